@@ -0,0 +1,106 @@
+//! Finds every day module under `src/days`, so that adding a day only means adding its file. Each
+//! module registers itself with `day!`, giving its own day number, so the order they're found in
+//! doesn't matter.
+//!
+//! The days of 2024 are kept directly in `src/days`, from before there were any other years. Every
+//! other year's are kept in a directory named after the year, like `src/days/2023`.
+
+use std::{
+    env, fs, io,
+    path::{Path, PathBuf},
+};
+
+const DAYS_DIR_PATH: &str = "src/days";
+
+/// The year whose day modules are kept directly in the days directory.
+const FLAT_LAYOUT_YEAR: u16 = 2024;
+
+fn main() -> io::Result<()> {
+    println!("cargo:rerun-if-changed={}", DAYS_DIR_PATH);
+
+    let days_dir = Path::new(env!("CARGO_MANIFEST_DIR")).join(DAYS_DIR_PATH);
+
+    let mut years = vec![(FLAT_LAYOUT_YEAR, days_dir.clone())];
+
+    for entry in fs::read_dir(&days_dir)?.filter_map(Result::ok) {
+        let year = entry
+            .file_name()
+            .to_str()
+            .and_then(|name| name.parse().ok());
+
+        if let (Some(year), true) = (year, entry.path().is_dir()) {
+            println!("cargo:rerun-if-changed={}/{}", DAYS_DIR_PATH, year);
+            years.push((year, entry.path()));
+        }
+    }
+
+    years.sort();
+
+    let mut generated = String::new();
+    let mut registered_years = Vec::new();
+
+    for (year, dir) in &years {
+        // Modules all live alongside each other, so other years' are named after their year too.
+        let prefix = match *year {
+            FLAT_LAYOUT_YEAR => String::new(),
+            year => format!("y{}_", year),
+        };
+
+        let modules = day_modules(dir)?;
+
+        for (module, path) in &modules {
+            generated.push_str(&format!(
+                "#[path = {:?}]\nmod {}{};\n",
+                path, prefix, module
+            ));
+        }
+
+        generated.push_str(&format!(
+            "\n/// Every day module for {}, in no particular order.\nconst REGISTERED_{}: [Day; {}] \
+             = [{}];\n\n",
+            year,
+            year,
+            modules.len(),
+            modules
+                .iter()
+                .map(|(module, _)| format!("{}{}::DAY", prefix, module))
+                .collect::<Vec<_>>()
+                .join(", ")
+        ));
+
+        registered_years.push(format!(
+            "Year {{ year: {}, days: &sort_days(REGISTERED_{}) }}",
+            year, year
+        ));
+    }
+
+    generated.push_str(&format!(
+        "/// Every year with a directory of day modules, in order.\nconst REGISTERED_YEARS: [Year; \
+         {}] = [{}];\n",
+        registered_years.len(),
+        registered_years.join(", ")
+    ));
+
+    let out_path = Path::new(&env::var("OUT_DIR").unwrap()).join("days.rs");
+    fs::write(out_path, generated)
+}
+
+/// The name and path of each day module in a directory, in order of name.
+fn day_modules(dir: &Path) -> io::Result<Vec<(String, PathBuf)>> {
+    let mut modules = fs::read_dir(dir)?
+        .filter_map(Result::ok)
+        .filter_map(|entry| {
+            let name = entry.file_name().into_string().ok()?;
+            let module = name.strip_suffix(".rs")?;
+
+            module
+                .strip_prefix("day")
+                .is_some_and(|number| number.parse::<usize>().is_ok())
+                .then(|| (module.to_string(), entry.path()))
+        })
+        .collect::<Vec<_>>();
+
+    modules.sort();
+
+    Ok(modules)
+}
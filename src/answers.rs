@@ -0,0 +1,185 @@
+//! Storage of the known-correct answers for each day, so that a run can tell whether a solution
+//! still produces the right results after being changed.
+//!
+//! Answers are stored as plain text, one file per day in a directory for each year, with the answer
+//! to part 1 on the first line and the answer to part 2 on the second. An empty line means the
+//! answer for that part isn't known yet, and won't be checked.
+
+use std::{
+    fs::{create_dir, File},
+    io::{self, Read, Write},
+    path::{Path, PathBuf},
+};
+
+use crate::{
+    days::{Example, Part},
+    input,
+    utils::NOT_YET_IMPLEMENTED,
+};
+
+const ANSWERS_DIR_PATH: &str = "answers";
+
+/// Get the path of the answers store for the given year, creating it if need be.
+pub fn init_answers_store(year: u16) -> Result<PathBuf, io::Error> {
+    let answers_store_path = PathBuf::from(ANSWERS_DIR_PATH);
+
+    if !answers_store_path.exists() {
+        create_dir(&answers_store_path)?;
+    }
+
+    input::init_year_dir(&answers_store_path, year)
+}
+
+/// The recorded answers for a single day. Either part may be missing if it hasn't been solved yet.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct ExpectedAnswers {
+    pub part1: Option<String>,
+    pub part2: Option<String>,
+}
+
+impl ExpectedAnswers {
+    pub fn for_part(&self, part: Part) -> Option<&str> {
+        match part {
+            Part::One => self.part1.as_deref(),
+            Part::Two => self.part2.as_deref(),
+        }
+    }
+
+    /// Check the answer produced for a part by a run against the one recorded.
+    pub fn verify(&self, part: Part, answer: &str) -> Verdict {
+        Verdict::of(self.for_part(part), answer)
+    }
+}
+
+impl From<&Example> for ExpectedAnswers {
+    fn from(example: &Example) -> Self {
+        ExpectedAnswers {
+            part1: example.part1.map(str::to_string),
+            part2: example.part2.map(str::to_string),
+        }
+    }
+}
+
+/// The outcome of checking a single part's answer against the recorded one.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Verdict {
+    /// No answer is recorded for this part, so we can't say either way.
+    Unknown,
+    Correct,
+    Incorrect {
+        expected: String,
+    },
+}
+
+impl Verdict {
+    fn of(expected: Option<&str>, actual: &str) -> Self {
+        match expected {
+            // A part that hasn't been solved yet hasn't got anything wrong.
+            _ if actual == NOT_YET_IMPLEMENTED => Verdict::Unknown,
+            None => Verdict::Unknown,
+            Some(expected) if expected == actual => Verdict::Correct,
+            Some(expected) => Verdict::Incorrect {
+                expected: expected.to_string(),
+            },
+        }
+    }
+
+    pub fn is_incorrect(&self) -> bool {
+        matches!(self, Verdict::Incorrect { .. })
+    }
+}
+
+/// Load the recorded answers for a day. If no answers have been recorded, `None` is returned.
+pub fn load_answers(
+    day: usize,
+    answers_store_path: &Path,
+) -> Result<Option<ExpectedAnswers>, io::Error> {
+    let answers_path = answers_store_path.join(format!("day_{}.txt", day));
+
+    let mut file = match File::open(&answers_path) {
+        Ok(file) => file,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(None),
+        Err(err) => return Err(err),
+    };
+
+    let mut contents = String::new();
+    file.read_to_string(&mut contents)?;
+
+    Ok(Some(parse_answers(&contents)))
+}
+
+/// Record the given answers for a day as being correct, replacing any that were there before.
+pub fn save_answers(
+    day: usize,
+    answers_store_path: &Path,
+    answers: &ExpectedAnswers,
+) -> Result<(), io::Error> {
+    let answers_path = answers_store_path.join(format!("day_{}.txt", day));
+
+    let mut file = File::create(answers_path)?;
+    writeln!(file, "{}", answers.part1.as_deref().unwrap_or_default())?;
+    writeln!(file, "{}", answers.part2.as_deref().unwrap_or_default())?;
+
+    Ok(())
+}
+
+fn parse_answers(contents: &str) -> ExpectedAnswers {
+    // The placeholder was saved by older versions for parts that hadn't been solved yet.
+    let mut lines = contents.lines().map(str::trim).map(|line| match line {
+        "" | NOT_YET_IMPLEMENTED => None,
+        answer => Some(answer.to_string()),
+    });
+
+    ExpectedAnswers {
+        part1: lines.next().flatten(),
+        part2: lines.next().flatten(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use indoc::indoc;
+
+    #[test]
+    fn parses_both_parts() {
+        let answers = parse_answers(indoc! {"
+            1234
+            5,6
+        "});
+
+        assert_eq!(answers.part1.as_deref(), Some("1234"));
+        assert_eq!(answers.part2.as_deref(), Some("5,6"));
+    }
+
+    #[test]
+    fn missing_parts_are_unknown() {
+        assert_eq!(parse_answers(""), ExpectedAnswers::default());
+        assert_eq!(
+            parse_answers("\n42\n"),
+            ExpectedAnswers {
+                part1: None,
+                part2: Some("42".to_string())
+            }
+        );
+
+        assert_eq!(
+            parse_answers(&format!("{}\n42\n", NOT_YET_IMPLEMENTED)),
+            parse_answers("\n42\n")
+        );
+
+        let answers = parse_answers("\n42\n");
+
+        assert_eq!(answers.verify(Part::One, "1"), Verdict::Unknown);
+        assert_eq!(
+            answers.verify(Part::Two, NOT_YET_IMPLEMENTED),
+            Verdict::Unknown
+        );
+        assert_eq!(
+            answers.verify(Part::Two, "41"),
+            Verdict::Incorrect {
+                expected: "42".to_string()
+            }
+        );
+    }
+}
@@ -0,0 +1,234 @@
+//! Repeatedly running a day to get timings that can be meaningfully compared between runs, rather
+//! than relying on a single noisy sample.
+
+use std::{
+    fmt::Display,
+    iter::Sum,
+    time::{Duration, Instant},
+};
+
+use crate::{
+    days::{Day, Params, Part},
+    runner::{self, BenchOptions, Failure, PartStep, Steps, Timing},
+};
+
+pub const DEFAULT_WARMUP_RUNS: usize = 3;
+pub const DEFAULT_TIMED_RUNS: usize = 20;
+
+/// Run a day's solution a number of times without recording anything, to let caches and the rayon
+/// thread pool settle, and then a number of times more to collect timing samples of each step.
+///
+/// Each part's outcome is that of its final run. If a part fails, it isn't run again, since the
+/// timings of a failing solution don't mean much. If parsing fails, there's nothing to time at all.
+pub fn bench_day(
+    day: &Day,
+    input: &str,
+    params: Option<&Params>,
+    parts: &[Part],
+    options: BenchOptions,
+) -> Result<Steps, Failure> {
+    for _ in 0..options.warmup_runs {
+        let parsed = runner::run_step(|| (day.parse)(input, params))?;
+
+        for &part in parts {
+            let _ = runner::run_step(|| day.part(part)(&parsed));
+        }
+    }
+
+    let runs = options.timed_runs.max(1);
+
+    let mut parse_samples = Vec::with_capacity(runs);
+    let mut part_samples = vec![Vec::with_capacity(runs); parts.len()];
+    let mut total_samples = Vec::with_capacity(runs);
+    let mut outcomes = parts.iter().map(|_| None).collect::<Vec<_>>();
+
+    for _ in 0..runs {
+        let now = Instant::now();
+        let parsed = runner::run_step(|| (day.parse)(input, params))?;
+        let mut total = now.elapsed();
+
+        parse_samples.push(total);
+
+        for (i, &part) in parts.iter().enumerate() {
+            if let Some(Err(_)) = outcomes[i] {
+                continue;
+            }
+
+            let now = Instant::now();
+            let outcome = runner::run_step(|| day.part(part)(&parsed));
+            let elapsed = now.elapsed();
+
+            part_samples[i].push(elapsed);
+            outcomes[i] = Some(outcome);
+            total += elapsed;
+        }
+
+        total_samples.push(total);
+    }
+
+    let parts = parts
+        .iter()
+        .zip(outcomes)
+        .zip(part_samples)
+        .map(|((&part, outcome), samples)| PartStep {
+            part,
+            outcome: outcome.expect("Every part is run at least once"),
+            timing: Timing::Bench(Stats::from_samples(samples)),
+            mem: None,
+        })
+        .collect();
+
+    Ok(Steps {
+        parse: Timing::Bench(Stats::from_samples(parse_samples)),
+        parse_mem: None,
+        parts,
+        total: Timing::Bench(Stats::from_samples(total_samples)),
+    })
+}
+
+/// Summary statistics over a set of timing samples.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Stats {
+    pub samples: usize,
+    pub min: Duration,
+    pub median: Duration,
+    pub mean: Duration,
+    pub p95: Duration,
+    pub stddev: Duration,
+}
+
+impl Stats {
+    pub fn from_samples(mut samples: Vec<Duration>) -> Self {
+        if samples.is_empty() {
+            return Stats::default();
+        }
+
+        samples.sort();
+
+        let count = samples.len();
+        let mean_secs = samples.iter().map(Duration::as_secs_f64).sum::<f64>() / count as f64;
+
+        let median = if count.is_multiple_of(2) {
+            (samples[count / 2 - 1] + samples[count / 2]) / 2
+        } else {
+            samples[count / 2]
+        };
+
+        // Nearest-rank percentile, so that the value is always one that was actually measured.
+        let p95 = samples[((count as f64 * 0.95).ceil() as usize).clamp(1, count) - 1];
+
+        let variance = if count > 1 {
+            samples
+                .iter()
+                .map(|sample| (sample.as_secs_f64() - mean_secs).powi(2))
+                .sum::<f64>()
+                / (count - 1) as f64
+        } else {
+            0.0
+        };
+
+        Stats {
+            samples: count,
+            min: samples[0],
+            median,
+            mean: Duration::from_secs_f64(mean_secs),
+            p95,
+            stddev: Duration::from_secs_f64(variance.sqrt()),
+        }
+    }
+}
+
+impl Display for Stats {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "min {}µs / median {}µs / mean {}µs / p95 {}µs / stddev {}µs ({} runs)",
+            self.min.as_micros(),
+            self.median.as_micros(),
+            self.mean.as_micros(),
+            self.p95.as_micros(),
+            self.stddev.as_micros(),
+            self.samples
+        )
+    }
+}
+
+/// Totals of the per-day statistics, to get an idea of how long running every day takes.
+///
+/// Only the min, median and mean are summed, as percentiles and deviations don't add up in any
+/// meaningful way.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Total {
+    pub days: usize,
+    pub min: Duration,
+    pub median: Duration,
+    pub mean: Duration,
+}
+
+impl<'a> Sum<&'a Stats> for Total {
+    fn sum<I: Iterator<Item = &'a Stats>>(iter: I) -> Self {
+        iter.fold(Total::default(), |total, stats| Total {
+            days: total.days + 1,
+            min: total.min + stats.min,
+            median: total.median + stats.median,
+            mean: total.mean + stats.mean,
+        })
+    }
+}
+
+impl Display for Total {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "min {}µs / median {}µs / mean {}µs ({} days)",
+            self.min.as_micros(),
+            self.median.as_micros(),
+            self.mean.as_micros(),
+            self.days
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn micros(values: &[u64]) -> Vec<Duration> {
+        values.iter().copied().map(Duration::from_micros).collect()
+    }
+
+    #[test]
+    fn stats_of_samples() {
+        let stats = Stats::from_samples(micros(&[40, 10, 30, 20]));
+
+        assert_eq!(stats.samples, 4);
+        assert_eq!(stats.min, Duration::from_micros(10));
+        assert_eq!(stats.median, Duration::from_micros(25));
+        assert_eq!(stats.mean.as_micros(), 25);
+        assert_eq!(stats.p95, Duration::from_micros(40));
+        assert_eq!(stats.stddev.as_nanos(), 12910);
+    }
+
+    #[test]
+    fn stats_of_single_sample() {
+        let stats = Stats::from_samples(micros(&[7]));
+
+        assert_eq!(stats.median, Duration::from_micros(7));
+        assert_eq!(stats.p95, Duration::from_micros(7));
+        assert_eq!(stats.stddev, Duration::ZERO);
+    }
+
+    #[test]
+    fn totals_sum_per_day_stats() {
+        let total: Total = [
+            Stats::from_samples(micros(&[10, 20, 30])),
+            Stats::from_samples(micros(&[5])),
+        ]
+        .iter()
+        .sum();
+
+        assert_eq!(total.days, 2);
+        assert_eq!(total.min, Duration::from_micros(15));
+        assert_eq!(total.median, Duration::from_micros(25));
+    }
+}
@@ -0,0 +1,326 @@
+//! The connection to Advent of Code, shared by everything that talks to it: downloading inputs and
+//! puzzles, and submitting answers.
+
+use std::{
+    error::Error,
+    fmt::{Debug, Display},
+    fs::OpenOptions,
+    io::{self, Read, Seek, SeekFrom, Write},
+    path::{Path, PathBuf},
+    sync::Arc,
+    thread,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use reqwest::{
+    blocking::{Client, RequestBuilder, Response},
+    header::RETRY_AFTER,
+    StatusCode,
+};
+
+/// Where Advent of Code lives, unless we're told otherwise.
+pub const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+
+/// How long to wait for Advent of Code to respond before giving up.
+pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Advent of Code asks tools to identify themselves, ideally with a way to contact whoever is
+/// running them, which only they can add.
+pub const DEFAULT_USER_AGENT: &str =
+    concat!(env!("CARGO_PKG_NAME"), "/", env!("CARGO_PKG_VERSION"));
+
+/// How long to leave between requests to AOC, which asks tools not to make them in quick
+/// succession.
+pub const DEFAULT_REQUEST_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Where a throttle keeps the time of the next request it'll allow, in the directory it's given.
+/// AOC limits requests to the whole site rather than to each year, so that's best shared by them.
+const THROTTLE_FILE_NAME: &str = ".next_request";
+
+/// How to talk to Advent of Code.
+#[derive(Debug, Clone)]
+pub struct ClientConfig {
+    pub base_url: String,
+    pub timeout: Duration,
+    pub user_agent: String,
+}
+
+impl Default for ClientConfig {
+    fn default() -> Self {
+        ClientConfig {
+            base_url: DEFAULT_BASE_URL.to_string(),
+            timeout: DEFAULT_TIMEOUT,
+            user_agent: DEFAULT_USER_AGENT.to_string(),
+        }
+    }
+}
+
+/// A client for Advent of Code, logged in with the session cookie if there is one.
+#[derive(Debug, Clone)]
+pub struct AocClient {
+    http: Client,
+    base_url: String,
+    cookie: Option<String>,
+    throttle: Option<Throttle>,
+    reporter: Reporter,
+}
+
+impl AocClient {
+    pub fn new(config: &ClientConfig, cookie: Option<String>) -> Result<Self, reqwest::Error> {
+        let http = Client::builder()
+            .timeout(config.timeout)
+            .user_agent(&config.user_agent)
+            .build()?;
+
+        Ok(AocClient {
+            http,
+            base_url: config.base_url.trim_end_matches('/').to_string(),
+            cookie: cookie.map(|cookie| cookie.trim().to_string()),
+            throttle: None,
+            reporter: Reporter::default(),
+        })
+    }
+
+    /// Space out the requests made with this client using `throttle`.
+    pub fn with_throttle(self, throttle: Throttle) -> Self {
+        AocClient {
+            throttle: Some(throttle),
+            ..self
+        }
+    }
+
+    /// Tell the user what the client is up to with `reporter`, rather than on stderr.
+    pub fn with_reporter(self, reporter: Reporter) -> Self {
+        AocClient { reporter, ..self }
+    }
+
+    /// Tell the user about something happening while talking to AOC, like a download starting.
+    pub fn report(&self, message: &str) {
+        self.reporter.report(message);
+    }
+
+    pub fn cookie(&self) -> Option<&str> {
+        self.cookie.as_deref()
+    }
+
+    /// Start a GET request for a path on the site, like `/2024/day/1`.
+    pub fn get(&self, path: &str) -> RequestBuilder {
+        self.wait_for_throttle();
+        self.with_cookie(self.http.get(self.url(path)))
+    }
+
+    /// Start a POST request for a path on the site, like `/2024/day/1/answer`.
+    pub fn post(&self, path: &str) -> RequestBuilder {
+        self.wait_for_throttle();
+        self.with_cookie(self.http.post(self.url(path)))
+    }
+
+    /// Make sure a response isn't Advent of Code telling us to slow down. If it is, the throttle
+    /// holds off the next request for as long as it asks.
+    pub fn check_rate_limit(&self, response: &Response) -> Result<(), TooManyRequests> {
+        if response.status() != StatusCode::TOO_MANY_REQUESTS {
+            return Ok(());
+        }
+
+        // Only the number of seconds to wait is handled, not the date to wait until.
+        let retry_after = response
+            .headers()
+            .get(RETRY_AFTER)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.trim().parse().ok())
+            .map(Duration::from_secs);
+
+        if let (Some(throttle), Some(retry_after)) = (&self.throttle, retry_after) {
+            if let Err(err) = throttle.defer(retry_after) {
+                self.report(&format!("Failed to hold off requests to AOC: {}", err));
+            }
+        }
+
+        Err(TooManyRequests { retry_after })
+    }
+
+    fn url(&self, path: &str) -> String {
+        format!("{}{}", self.base_url, path)
+    }
+
+    fn wait_for_throttle(&self) {
+        if let Some(throttle) = &self.throttle {
+            if let Err(err) = throttle.wait(&self.reporter) {
+                self.report(&format!("Failed to throttle requests to AOC: {}", err));
+            }
+        }
+    }
+
+    fn with_cookie(&self, request: RequestBuilder) -> RequestBuilder {
+        match &self.cookie {
+            Some(cookie) => request.header(reqwest::header::COOKIE, cookie),
+            None => request,
+        }
+    }
+}
+
+/// Spaces out requests to AOC, across every process sharing the same directory, by keeping the
+/// time of the next request it'll allow in a file there.
+#[derive(Debug, Clone)]
+pub struct Throttle {
+    path: PathBuf,
+    interval: Duration,
+}
+
+impl Throttle {
+    pub fn new(dir: &Path, interval: Duration) -> Self {
+        Throttle {
+            path: dir.join(THROTTLE_FILE_NAME),
+            interval,
+        }
+    }
+
+    /// Wait until another request can be made, and hold off the one after it for the interval.
+    pub fn wait(&self, reporter: &Reporter) -> Result<(), io::Error> {
+        self.update(|next_request, now| {
+            if let Some(wait) = next_request.checked_sub(now).filter(|wait| !wait.is_zero()) {
+                reporter.report(&format!(
+                    "Waiting {} before the next request to AOC...",
+                    humantime::format_duration(Duration::from_secs(
+                        wait.as_secs_f64().ceil() as u64
+                    ))
+                ));
+                thread::sleep(wait);
+            }
+
+            since_epoch() + self.interval
+        })
+    }
+
+    /// Hold off the next request until at least `delay` from now, such as when AOC asks us to.
+    pub fn defer(&self, delay: Duration) -> Result<(), io::Error> {
+        self.update(|next_request, now| next_request.max(now + delay))
+    }
+
+    /// Replace the time of the next request with what `update` makes of it and the current time,
+    /// keeping the file locked meanwhile so other processes wait their turn.
+    fn update(&self, update: impl FnOnce(Duration, Duration) -> Duration) -> Result<(), io::Error> {
+        let mut file = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(false)
+            .open(&self.path)?;
+        file.lock()?;
+
+        let mut contents = String::new();
+        file.read_to_string(&mut contents)?;
+
+        // Kept to the nanosecond, as rounding it would let requests through early.
+        let next_request = contents
+            .trim()
+            .parse()
+            .map_or(Duration::ZERO, Duration::from_nanos);
+        let next_request = update(next_request, since_epoch());
+
+        file.set_len(0)?;
+        file.seek(SeekFrom::Start(0))?;
+        write!(file, "{}", next_request.as_nanos())
+    }
+}
+
+fn since_epoch() -> Duration {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+}
+
+/// Where the client tells the user what it's up to, such as waiting for the throttle or
+/// downloading something. Unless given somewhere else, that's stderr.
+#[derive(Clone)]
+pub struct Reporter(Arc<dyn Fn(&str) + Send + Sync>);
+
+impl Reporter {
+    pub fn new(report: impl Fn(&str) + Send + Sync + 'static) -> Self {
+        Reporter(Arc::new(report))
+    }
+
+    pub fn report(&self, message: &str) {
+        (self.0)(message)
+    }
+}
+
+impl Default for Reporter {
+    fn default() -> Self {
+        Reporter::new(|message| eprintln!("{}", message))
+    }
+}
+
+impl Debug for Reporter {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("Reporter")
+    }
+}
+
+/// Advent of Code responded that we're making too many requests.
+#[derive(Debug)]
+pub struct TooManyRequests {
+    /// How long it asked us to wait before trying again, if it said.
+    pub retry_after: Option<Duration>,
+}
+
+impl Display for TooManyRequests {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.retry_after {
+            Some(retry_after) => write!(
+                f,
+                "AOC is getting too many requests from us, wait {} before trying again",
+                humantime::format_duration(retry_after)
+            ),
+            None => write!(
+                f,
+                "AOC is getting too many requests from us, wait a while before trying again"
+            ),
+        }
+    }
+}
+
+impl Error for TooManyRequests {}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        sync::{Arc, Mutex},
+        time::Instant,
+    };
+
+    use super::*;
+    use crate::support::temp_dir::TempDir;
+
+    #[test]
+    fn throttles_requests() {
+        let cache = TempDir::new("throttle");
+
+        let reported = Arc::new(Mutex::new(Vec::new()));
+        let reporter = Reporter::new({
+            let reported = Arc::clone(&reported);
+            move |message| reported.lock().unwrap().push(message.to_string())
+        });
+
+        let throttle = Throttle::new(cache.path(), Duration::from_millis(200));
+        let start = Instant::now();
+
+        throttle.wait(&reporter).unwrap();
+        assert!(start.elapsed() < Duration::from_millis(200));
+        assert!(reported.lock().unwrap().is_empty());
+
+        // Another process would see the same file, so a new throttle has to wait too.
+        Throttle::new(cache.path(), Duration::from_millis(200))
+            .wait(&reporter)
+            .unwrap();
+        assert!(start.elapsed() >= Duration::from_millis(200));
+        assert_eq!(
+            *reported.lock().unwrap(),
+            ["Waiting 1s before the next request to AOC..."]
+        );
+
+        throttle.defer(Duration::from_millis(500)).unwrap();
+        throttle.wait(&reporter).unwrap();
+        assert!(start.elapsed() >= Duration::from_millis(700));
+    }
+}
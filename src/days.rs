@@ -1,48 +1,268 @@
-mod day1;
-mod day10;
-mod day11;
-mod day12;
-mod day13;
-mod day14;
-mod day15;
-mod day16;
-mod day17;
-mod day18;
-mod day19;
-mod day2;
-mod day3;
-mod day4;
-mod day5;
-mod day6;
-mod day7;
-mod day8;
-mod day9;
-
 use core::str;
-use std::error::Error;
-
-pub(crate) type PartResult = Result<String, Box<dyn Error>>;
-pub(crate) type DayResult = Result<(String, String), Box<dyn Error>>;
-pub(crate) type DayFunc = fn(&str) -> DayResult;
-
-pub(crate) const DAYS: &[DayFunc] = &[
-    day1::solve,
-    day2::solve,
-    day3::solve,
-    day4::solve,
-    day5::solve,
-    day6::solve,
-    day7::solve,
-    day8::solve,
-    day9::solve,
-    day10::solve,
-    day11::solve,
-    day12::solve,
-    day13::solve,
-    day14::solve,
-    day15::solve,
-    day16::solve,
-    day17::solve,
-    day18::solve,
-    day19::solve,
-];
+use std::{any::Any, error::Error};
+
+/// Why a day's solution failed. These need to be sendable, so that days can be run on other threads.
+pub type SolutionError = Box<dyn Error + Send + Sync>;
+
+pub type PartResult = Result<String, SolutionError>;
+pub type ParseResult<T> = Result<T, SolutionError>;
+
+/// A day's input after parsing, in whatever form that day's parts expect it.
+pub type Parsed = Box<dyn Any>;
+
+/// Values a day's solution would otherwise hardcode for the real input, in whatever form that day
+/// expects them. Only given for examples which need something different to the real input.
+pub type Params = dyn Any + Send + Sync;
+
+/// A day's solution, split up into parsing the input, and solving each of the parts from the
+/// parsed input, so that each step can be run and timed on its own.
+///
+/// Built from a day module's `parse`, `part1` and `part2` functions and its `EXAMPLES` using [day!].
+#[derive(Clone, Copy)]
+pub struct Day {
+    /// Which day of the year's puzzles this solves.
+    pub number: usize,
+    /// The title of the day's puzzle, if we've written it down.
+    pub title: Option<&'static str>,
+    pub parse: fn(&str, Option<&Params>) -> ParseResult<Parsed>,
+    pub part1: fn(&Parsed) -> PartResult,
+    pub part2: fn(&Parsed) -> PartResult,
+    pub examples: &'static [Example],
+}
+
+impl Day {
+    pub fn part(&self, part: Part) -> fn(&Parsed) -> PartResult {
+        match part {
+            Part::One => self.part1,
+            Part::Two => self.part2,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Part {
+    One,
+    Two,
+}
+
+impl Part {
+    pub const ALL: [Part; 2] = [Part::One, Part::Two];
+
+    /// The number of the part, as the site and our records refer to it.
+    pub fn number(self) -> u8 {
+        match self {
+            Part::One => 1,
+            Part::Two => 2,
+        }
+    }
+}
+
+/// An example input from a day's puzzle description, along with the answers the puzzle gives for
+/// it.
+pub struct Example {
+    pub input: &'static str,
+    /// Parts without an answer given aren't run on the example, as their solutions may not even
+    /// terminate on it.
+    pub part1: Option<&'static str>,
+    pub part2: Option<&'static str>,
+    /// Replacements for the values the day would otherwise use for the real input.
+    pub params: Option<&'static Params>,
+}
+
+impl Example {
+    pub const fn new(
+        input: &'static str,
+        part1: Option<&'static str>,
+        part2: Option<&'static str>,
+    ) -> Self {
+        Example {
+            input,
+            part1,
+            part2,
+            params: None,
+        }
+    }
+
+    pub const fn with_params(self, params: &'static Params) -> Self {
+        Example {
+            params: Some(params),
+            ..self
+        }
+    }
+
+    pub fn answer(&self, part: Part) -> Option<&'static str> {
+        match part {
+            Part::One => self.part1,
+            Part::Two => self.part2,
+        }
+    }
+}
+
+/// Register a day module as the solution for a day, erasing the types of its parsed input so that
+/// its `parse`, `part1` and `part2` functions can be stored alongside all the other days'.
+///
+/// Called from within the day module as `day!(N, "Title");`, with the title optional. Days which
+/// need parameters add `with params`, and their `parse` takes the module's own `Params` type, using
+/// its `PARAMS` for the real input.
+macro_rules! day {
+    ($number:literal $(, $title:literal)?) => {
+        day!(@register $number, $($title)?, |input, _params: Option<&$crate::days::Params>| {
+            parse(input)
+        });
+    };
+    ($number:literal $(, $title:literal)?, with params) => {
+        day!(@register $number, $($title)?, |input, params: Option<&$crate::days::Params>| {
+            parse(input, $crate::days::downcast_params(params, &PARAMS))
+        });
+    };
+    (@register $number:literal, $($title:literal)?, $parse:expr) => {
+        pub(crate) const DAY: $crate::days::Day = $crate::days::Day {
+            number: $number,
+            title: day!(@title $($title)?),
+            parse: |input, params| Ok(Box::new(($parse)(input, params)?)),
+            part1: |parsed| {
+                let parsed =
+                    $crate::days::downcast_parsed(parsed, |input| ($parse)(input, None));
+                part1(parsed)
+            },
+            part2: |parsed| {
+                let parsed =
+                    $crate::days::downcast_parsed(parsed, |input| ($parse)(input, None));
+                part2(parsed)
+            },
+            examples: EXAMPLES,
+        };
+    };
+    (@title) => {
+        None
+    };
+    (@title $title:literal) => {
+        Some($title)
+    };
+}
+
+// Declares every module in `days/`, and gathers up their `DAY`s. See `build.rs`.
+include!(concat!(env!("OUT_DIR"), "/days.rs"));
+
+/// Recover the concrete type of a day's parsed input, as given by the day's `parse` function.
+fn downcast_parsed<T: 'static>(parsed: &Parsed, _parse: fn(&str) -> ParseResult<T>) -> &T {
+    parsed
+        .downcast_ref()
+        .expect("Parsed input should be of the type the day parses it to")
+}
+
+/// Recover the concrete type of an example's parameters, or use those for the real input if the
+/// example doesn't have any.
+fn downcast_params<'a, P: 'static>(params: Option<&'a Params>, default: &'a P) -> &'a P {
+    match params {
+        Some(params) => params
+            .downcast_ref()
+            .expect("Example parameters should be of the type the day expects"),
+        None => default,
+    }
+}
+
+/// The solutions for a single year's puzzles.
+pub struct Year {
+    pub year: u16,
+    /// The solution for each day, in order of day. Days without a solution yet are left out.
+    pub days: &'static [Day],
+}
+
+impl Year {
+    pub fn find_day(&self, number: usize) -> Option<&'static Day> {
+        self.days.iter().find(|day| day.number == number)
+    }
+}
+
+/// Every year we have solutions for, in order.
+pub const YEARS: &[Year] = &REGISTERED_YEARS;
+
+pub fn find_year(year: u16) -> Option<&'static Year> {
+    YEARS.iter().find(|registry| registry.year == year)
+}
+
+/// The most recent year we have solutions for.
+pub fn latest_year() -> u16 {
+    YEARS
+        .last()
+        .expect("There should be at least one year")
+        .year
+}
+
+/// The last day of each year's puzzles.
+pub const LAST_DAY: usize = 25;
+
+/// Put days in order, checking that they're each registered as a real day, and only once. As this
+/// is done when compiling, any mistakes stop the build.
+const fn sort_days<const N: usize>(mut days: [Day; N]) -> [Day; N] {
+    let mut i = 0;
+
+    while i < N {
+        if days[i].number < 1 || days[i].number > LAST_DAY {
+            panic!("A day module is registered with a day number outside of 1 to 25!");
+        }
+
+        let mut j = i;
+
+        while j > 0 && days[j - 1].number >= days[j].number {
+            if days[j - 1].number == days[j].number {
+                panic!("Two day modules are registered as the same day!");
+            }
+
+            days.swap(j - 1, j);
+            j -= 1;
+        }
+
+        i += 1;
+    }
+
+    days
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn day(number: usize) -> Day {
+        Day {
+            number,
+            title: None,
+            parse: |_, _| Ok(Box::new(())),
+            part1: |_| Ok(String::new()),
+            part2: |_| Ok(String::new()),
+            examples: &[],
+        }
+    }
+
+    fn numbers(days: &[Day]) -> Vec<usize> {
+        days.iter().map(|day| day.number).collect()
+    }
+
+    #[test]
+    fn sorts_days_leaving_gaps() {
+        assert_eq!(
+            numbers(&sort_days([day(14), day(1), day(25), day(5)])),
+            [1, 5, 14, 25]
+        );
+        assert_eq!(numbers(&sort_days([])), [] as [usize; 0]);
+    }
+
+    #[test]
+    #[should_panic(expected = "Two day modules are registered as the same day!")]
+    fn rejects_duplicate_days() {
+        sort_days([day(3), day(7), day(3)]);
+    }
+
+    #[test]
+    #[should_panic(expected = "outside of 1 to 25")]
+    fn rejects_days_outside_the_year() {
+        sort_days([day(1), day(26)]);
+    }
+
+    #[test]
+    #[should_panic(expected = "outside of 1 to 25")]
+    fn rejects_day_zero() {
+        sort_days([day(0)]);
+    }
+}
@@ -1,30 +1,37 @@
 use std::collections::HashMap;
 
-use super::{DayResult, PartResult};
-
-pub(crate) fn solve(input: &str) -> DayResult {
-    Ok((part1(input)?, part2(input)?))
+use indoc::indoc;
+
+use super::{Example, ParseResult, PartResult};
+
+day!(1, "Historian Hysteria");
+
+pub(crate) const EXAMPLES: &[Example] = &[Example::new(
+    indoc! {"
+        3   4
+        4   3
+        2   5
+        1   3
+        3   9
+        3   3
+    "},
+    Some("11"),
+    Some("31"),
+)];
+
+pub(crate) fn parse(input: &str) -> ParseResult<(Vec<usize>, Vec<usize>)> {
+    Ok(input.lines().map(parse_line).unzip())
 }
 
-fn part1(input: &str) -> PartResult {
-    // 1. Create two arrays.
-    // 2. Iterate over each line of input, putting LHS of whitespace into arr1, RHS into arr2 (converted to numbers.)
-    // 3. Get the smallest of both arrays, += the absolute difference to output.
-    // 4. Print output.
+pub(crate) fn part1((left, right): &(Vec<usize>, Vec<usize>)) -> PartResult {
+    // 1. Copy the LHS and RHS lists into two arrays we can sort.
+    // 2. Get the smallest of both arrays, += the absolute difference to output.
+    // 3. Print output.
 
-    let mut arr1 = Vec::<i32>::new();
-    let mut arr2 = Vec::<i32>::new();
+    let mut arr1 = left.clone();
+    let mut arr2 = right.clone();
     let mut sum = 0;
 
-    for line in input.lines() {
-        let mut split = line.split_whitespace();
-        let lhs = split.next().unwrap().parse::<i32>()?;
-        let rhs = split.next().unwrap().parse::<i32>()?;
-
-        arr1.push(lhs);
-        arr2.push(rhs);
-    }
-
     arr1.sort();
     arr2.sort();
 
@@ -36,10 +43,9 @@ fn part1(input: &str) -> PartResult {
     Ok(sum.to_string())
 }
 
-fn part2(input: &str) -> PartResult {
+pub(crate) fn part2((left, right): &(Vec<usize>, Vec<usize>)) -> PartResult {
     let mut left_occurrences: HashMap<usize, usize> = HashMap::new();
     let mut right_occurrences: HashMap<usize, usize> = HashMap::new();
-    let (left, right): (Vec<usize>, Vec<usize>) = input.lines().map(parse_line).unzip();
 
     left.iter().for_each(|value| {
         left_occurrences
@@ -1,5 +1,6 @@
 use std::vec;
 
+use indoc::indoc;
 use itertools::Itertools;
 use rustc_hash::FxHashSet;
 
@@ -8,9 +9,26 @@ use crate::utils::{
     pos::{Index2d, Pos},
 };
 
-use super::{DayResult, PartResult};
-
-pub(crate) fn solve(input: &str) -> DayResult {
+use super::{Example, ParseResult, PartResult};
+
+day!(10, "Hoof It");
+
+pub(crate) const EXAMPLES: &[Example] = &[Example::new(
+    indoc! {"
+        89010123
+        78121874
+        87430965
+        96549874
+        45678903
+        32019012
+        01329801
+        10456732
+    "},
+    Some("36"),
+    Some("81"),
+)];
+
+pub(crate) fn parse(input: &str) -> ParseResult<Vec<Vec<u32>>> {
     let grid = input
         .lines()
         .map(|line| {
@@ -20,10 +38,10 @@ pub(crate) fn solve(input: &str) -> DayResult {
         })
         .collect_vec();
 
-    Ok((part1(&grid)?, part2(&grid)?))
+    Ok(grid)
 }
 
-fn part1(grid: &Vec<Vec<u32>>) -> PartResult {
+pub(crate) fn part1(grid: &Vec<Vec<u32>>) -> PartResult {
     let mut seen_peaks = FxHashSet::<Pos>::default();
     let mut sum = 0;
 
@@ -45,7 +63,7 @@ fn part1(grid: &Vec<Vec<u32>>) -> PartResult {
     Ok(sum.to_string())
 }
 
-fn part2(grid: &Vec<Vec<u32>>) -> PartResult {
+pub(crate) fn part2(grid: &Vec<Vec<u32>>) -> PartResult {
     let mut sum = 0;
 
     for (y, row) in grid.iter().enumerate() {
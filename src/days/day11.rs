@@ -1,7 +1,11 @@
-use super::{DayResult, PartResult};
+use super::{Example, ParseResult, PartResult};
 use rustc_hash::FxHashMap;
 
-pub(crate) fn solve(input: &str) -> DayResult {
+day!(11, "Plutonian Pebbles");
+
+pub(crate) const EXAMPLES: &[Example] = &[Example::new("125 17", Some("55312"), None)];
+
+pub(crate) fn parse(input: &str) -> ParseResult<FxHashMap<u64, u64>> {
     let mut stones = FxHashMap::<u64, u64>::default();
 
     input
@@ -17,23 +21,24 @@ pub(crate) fn solve(input: &str) -> DayResult {
                 .or_insert(1);
         });
 
-    let result_part1 = part1(&mut stones)?;
-    let result_part2 = part2(&mut stones)?;
-
-    Ok((result_part1, result_part2))
+    Ok(stones)
 }
 
-fn part1(stones: &mut FxHashMap<u64, u64>) -> PartResult {
+pub(crate) fn part1(stones: &FxHashMap<u64, u64>) -> PartResult {
+    let mut stones = stones.clone();
+
     for _ in 1..=25 {
-        blink(stones);
+        blink(&mut stones);
     }
 
     Ok(stones.values().sum::<u64>().to_string())
 }
 
-fn part2(stones: &mut FxHashMap<u64, u64>) -> PartResult {
-    for _ in 1..=50 {
-        blink(stones);
+pub(crate) fn part2(stones: &FxHashMap<u64, u64>) -> PartResult {
+    let mut stones = stones.clone();
+
+    for _ in 1..=75 {
+        blink(&mut stones);
     }
 
     Ok(stones.values().sum::<u64>().to_string())
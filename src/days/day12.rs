@@ -1,3 +1,4 @@
+use indoc::indoc;
 use itertools::Itertools;
 
 use crate::utils::{
@@ -5,28 +6,39 @@ use crate::utils::{
     pos::{Index2d, Pos},
 };
 
-use super::{DayResult, PartResult};
-
-pub(crate) fn solve(input: &str) -> DayResult {
-    let mut grid = input
+use super::{Example, ParseResult, PartResult};
+
+day!(12, "Garden Groups");
+
+pub(crate) const EXAMPLES: &[Example] = &[Example::new(
+    indoc! {"
+        RRRRIICCFF
+        RRRRIICCCF
+        VVRRRCCFFF
+        VVRCCCJFFF
+        VVVVCJJCFE
+        VVIVCCJJEE
+        VVIIICJJEE
+        MIIIIIJJEE
+        MIIISIJEEE
+        MMMISSJEEE
+    "},
+    Some("1930"),
+    Some("1206"),
+)];
+
+pub(crate) fn parse(input: &str) -> ParseResult<Vec<Vec<Plant>>> {
+    let grid = input
         .lines()
         .map(|line| line.bytes().map(Plant::new).collect_vec())
         .collect_vec();
 
-    let p1_result = part1(&mut grid)?;
-
-    grid.iter_mut().for_each(|row| {
-        row.iter_mut().for_each(|plant| {
-            plant.seen = false;
-        })
-    });
-
-    let p2_result = part2(&mut grid)?;
-
-    Ok((p1_result, p2_result))
+    Ok(grid)
 }
 
-fn part1(grid: &mut Vec<Vec<Plant>>) -> PartResult {
+pub(crate) fn part1(grid: &[Vec<Plant>]) -> PartResult {
+    // Each part marks off the plants it has seen as it goes, so needs its own copy of the garden.
+    let mut grid = grid.to_vec();
     let mut sum: u64 = 0;
 
     for y in 0..grid.len() {
@@ -41,7 +53,7 @@ fn part1(grid: &mut Vec<Vec<Plant>>) -> PartResult {
             let mut perimeter = 0;
             let mut area = 0;
 
-            define_region(grid, plant.species, pos, &mut perimeter, &mut area);
+            define_region(&mut grid, plant.species, pos, &mut perimeter, &mut area);
 
             sum += area * perimeter;
         }
@@ -50,7 +62,8 @@ fn part1(grid: &mut Vec<Vec<Plant>>) -> PartResult {
     Ok(sum.to_string())
 }
 
-fn part2(grid: &mut Vec<Vec<Plant>>) -> PartResult {
+pub(crate) fn part2(grid: &[Vec<Plant>]) -> PartResult {
+    let mut grid = grid.to_vec();
     let mut sum: u64 = 0;
 
     for y in 0..grid.len() {
@@ -66,7 +79,7 @@ fn part2(grid: &mut Vec<Vec<Plant>>) -> PartResult {
             let mut corners = 0;
             let mut area = 0;
 
-            define_region_p2(grid, plant.species, pos, &mut corners, &mut area);
+            define_region_p2(&mut grid, plant.species, pos, &mut corners, &mut area);
 
             sum += area * corners;
         }
@@ -148,15 +161,14 @@ fn define_region_p2(
                 // Convex corner.
                 *corners += 1;
             }
-            (false, false) => {
-                // Concave corner.
+            (false, false)
                 if grid
                     .get_2d(pos + corner)
                     .map(|plant| plant.species != species)
-                    .unwrap_or(true)
-                {
-                    *corners += 1;
-                }
+                    .unwrap_or(true) =>
+            {
+                // Concave corner.
+                *corners += 1;
             }
             _ => {}
         }
@@ -167,7 +179,7 @@ fn define_region_p2(
 }
 
 #[derive(Clone, Copy)]
-struct Plant {
+pub(crate) struct Plant {
     species: u8,
     seen: bool,
 }
@@ -1,7 +1,32 @@
-use super::{DayResult, PartResult};
+use super::{Example, ParseResult, PartResult};
+use indoc::indoc;
 use itertools::Itertools;
 
-pub(crate) fn solve(input: &str) -> DayResult {
+day!(13, "Claw Contraption");
+
+pub(crate) const EXAMPLES: &[Example] = &[Example::new(
+    indoc! {"
+        Button A: X+94, Y+34
+        Button B: X+22, Y+67
+        Prize: X=8400, Y=5400
+
+        Button A: X+26, Y+66
+        Button B: X+67, Y+21
+        Prize: X=12748, Y=12176
+
+        Button A: X+17, Y+86
+        Button B: X+84, Y+37
+        Prize: X=7870, Y=6450
+
+        Button A: X+69, Y+23
+        Button B: X+27, Y+71
+        Prize: X=18641, Y=10279
+    "},
+    Some("480"),
+    None,
+)];
+
+pub(crate) fn parse(input: &str) -> ParseResult<Vec<Machine>> {
     let machines = input
         .lines()
         .filter(|line| !line.is_empty())
@@ -23,13 +48,13 @@ pub(crate) fn solve(input: &str) -> DayResult {
         })
         .collect_vec();
 
-    Ok((part1(&machines)?, part2(&machines)?))
+    Ok(machines)
 }
 
 const A_COST: i64 = 3;
 const B_COST: i64 = 1;
 
-fn part1(machines: &[Machine]) -> PartResult {
+pub(crate) fn part1(machines: &[Machine]) -> PartResult {
     const MAX_PRESSES: i64 = 100;
 
     let spent_tokens = machines
@@ -51,7 +76,7 @@ fn part1(machines: &[Machine]) -> PartResult {
     Ok(spent_tokens.to_string())
 }
 
-fn part2(machines: &[Machine]) -> PartResult {
+pub(crate) fn part2(machines: &[Machine]) -> PartResult {
     let spent_tokens = machines
         .iter()
         .filter_map(|machine| {
@@ -92,7 +117,7 @@ fn calculate_ratios(px: i64, py: i64, ax: i64, ay: i64, bx: i64, by: i64) -> Opt
 }
 
 #[derive(Debug)]
-struct Machine {
+pub(crate) struct Machine {
     a: BigPos,
     b: BigPos,
     prize: BigPos,
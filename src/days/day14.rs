@@ -1,12 +1,51 @@
 
+use indoc::indoc;
 use itertools::Itertools;
 
-use super::{DayResult, PartResult};
+use super::{Example, ParseResult, PartResult};
 use crate::utils::pos::Pos;
 
-const GRID_DIMENSIONS: Pos = Pos { x: 101, y: 103 };
+day!(14, "Restroom Redoubt", with params);
 
-pub(crate) fn solve(input: &str) -> DayResult {
+/// The size of the area the robots move around.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Params {
+    grid_dimensions: Pos,
+}
+
+pub(crate) const PARAMS: Params = Params {
+    grid_dimensions: Pos { x: 101, y: 103 },
+};
+
+pub(crate) const EXAMPLES: &[Example] = &[Example::new(
+    indoc! {"
+        p=0,4 v=3,-3
+        p=6,3 v=-1,-3
+        p=10,3 v=-1,2
+        p=2,0 v=2,-1
+        p=0,0 v=1,3
+        p=3,0 v=-2,-2
+        p=7,6 v=-1,-3
+        p=3,0 v=-1,-2
+        p=9,3 v=2,3
+        p=7,3 v=-1,2
+        p=2,4 v=2,-3
+        p=9,5 v=-3,-3
+    "},
+    Some("12"),
+    None,
+)
+.with_params(&Params {
+    grid_dimensions: Pos { x: 11, y: 7 },
+})];
+
+/// The robots, and the area they're moving around.
+pub(crate) struct Lobby {
+    robots: Vec<Robot>,
+    grid_dimensions: Pos,
+}
+
+pub(crate) fn parse(input: &str, params: &Params) -> ParseResult<Lobby> {
     let robots: Vec<Robot> = input
         .lines()
         .map(|line| {
@@ -25,21 +64,24 @@ pub(crate) fn solve(input: &str) -> DayResult {
         })
         .collect_vec();
 
-    Ok((part1(&robots)?, part2(&robots)?))
+    Ok(Lobby {
+        robots,
+        grid_dimensions: params.grid_dimensions,
+    })
 }
 
-fn part1(robots: &[Robot]) -> PartResult {
-    Ok(calc_safety_factor(robots, 100).to_string())
+pub(crate) fn part1(lobby: &Lobby) -> PartResult {
+    Ok(calc_safety_factor(&lobby.robots, 100, lobby.grid_dimensions).to_string())
 }
 
-fn part2(robots: &[Robot]) -> PartResult {
+pub(crate) fn part2(lobby: &Lobby) -> PartResult {
     // TODO: ideally not a bruteforce but better than nothing!!! we'll come back to this.
-    const STEPS_TILL_LOOP: i32 = GRID_DIMENSIONS.x * GRID_DIMENSIONS.y;
+    let steps_till_loop = lobby.grid_dimensions.x * lobby.grid_dimensions.y;
 
     let mut lowest_safety_factor = (i32::MAX, i32::MAX);
 
-    for steps in 0..STEPS_TILL_LOOP {
-        let safety_factor = calc_safety_factor(robots, steps);
+    for steps in 0..steps_till_loop {
+        let safety_factor = calc_safety_factor(&lobby.robots, steps, lobby.grid_dimensions);
 
         if safety_factor < lowest_safety_factor.0 {
             lowest_safety_factor = (safety_factor, steps);
@@ -49,16 +91,16 @@ fn part2(robots: &[Robot]) -> PartResult {
     Ok(lowest_safety_factor.1.to_string())
 }
 
-fn calc_safety_factor(robots: &[Robot], steps: i32) -> i32 {
+fn calc_safety_factor(robots: &[Robot], steps: i32, grid_dimensions: Pos) -> i32 {
     let mut top_left = 0;
     let mut top_right = 0;
     let mut bottom_left = 0;
     let mut bottom_right = 0;
 
     for robot in robots {
-        let forecast_pos = robot.move_steps(steps, GRID_DIMENSIONS);
+        let forecast_pos = robot.move_steps(steps, grid_dimensions);
 
-        let Some(quadrant) = Quadrant::of_pos(forecast_pos, GRID_DIMENSIONS) else {
+        let Some(quadrant) = Quadrant::of_pos(forecast_pos, grid_dimensions) else {
             continue;
         };
 
@@ -74,7 +116,7 @@ fn calc_safety_factor(robots: &[Robot], steps: i32) -> i32 {
 }
 
 #[derive(Debug, Clone, Copy)]
-struct Robot {
+pub(crate) struct Robot {
     pos: Pos,
     velocity: Pos,
 }
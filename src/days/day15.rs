@@ -1,3 +1,4 @@
+use indoc::indoc;
 use itertools::Itertools;
 use rustc_hash::FxHashSet;
 
@@ -6,13 +7,51 @@ use crate::utils::{
     pos::{Index2d, Pos},
 };
 
-use super::{DayResult, PartResult};
+use super::{Example, ParseResult, PartResult};
 
-pub(crate) fn solve(input: &str) -> DayResult {
+day!(15, "Warehouse Woes");
+
+/// The warehouse, in both its regular and double-width forms, and the moves the robot attempts.
+pub(crate) struct Warehouse {
+    p1_grid: Vec<Vec<Tile>>,
+    p2_grid: Vec<Vec<DWTile>>,
+    moves: Vec<Direction>,
+    robot_initial_pos: Pos,
+}
+
+pub(crate) const EXAMPLES: &[Example] = &[Example::new(
+    indoc! {"
+        ##########
+        #..O..O.O#
+        #......O.#
+        #.OO..O.O#
+        #..O@..O.#
+        #O#..O...#
+        #O..O..O.#
+        #.OO.O.OO#
+        #....O...#
+        ##########
+
+        <vv>^<v^>v>^vv^v>v<>v^v<v<^vv<<<^><<><>>v<vvv<>^v^>^<<<><<v<<<v^vv^v>^
+        vvv<<^>^v^^><<>>><>^<<><^vv^^<>vvv<>><^^v>^>vv<>v<<<<v<^v>^<^^>>>^<v<v
+        ><>vv>v^v^<>><>>>><^^>vv>v<^^^>>v^v^<^^>v^^>v^<^v>v<>>v^v^<v>v^^<^^vv<
+        <<v<^>>^^^^>>>v^<>vvv^><v<<<>^^^vv^<vvv>^>v<^^^^v<>^>vvvv><>>v^<<^^^^^
+        ^><^><>>><>^^<<^^v>>><^<v>^<vv>>v>>>^v><>^v><<<<v>>v<v<v>vvv>^<><<>^><
+        ^>><>^v<><^vvv<^^<><v<<<<<><^v<<<><<<^^<v<^^^><^>>^<v^><<<^>>^v<v^v<v^
+        >^>>^v>vv>^<<^v<>><<><<v<<v><>v<^vv<<<>^^v^>^^>>><<^v>>v^v><^^>>^<>vv^
+        <><^^>^^^<><vvvvv^v<v<<>^v<v>v<<^><<><<><<<^^<<<^<<>><<><^^^>^^<>^>v<>
+        ^^>vv<^v^v<vv>^<><v<^v>^^^>>>^^vvv^>vvv<>>>^<^>>>>>^<<^v>^vvv<>^<><<v>
+        v^^>>><<^^<>>^v^<v^vv<>v^<<>^<^v^v><^<<<><<^<v><v<>vv>>v><v^<vv<>v^<<^
+    "},
+    Some("10092"),
+    Some("9021"),
+)];
+
+pub(crate) fn parse(input: &str) -> ParseResult<Warehouse> {
     let mut robot_pos = Pos { x: 0, y: 0 };
 
     let mut lines = input.lines();
-    let mut p1_grid = lines
+    let p1_grid = lines
         .by_ref()
         .take_while(|line| !line.is_empty())
         .enumerate()
@@ -33,7 +72,7 @@ pub(crate) fn solve(input: &str) -> DayResult {
         })
         .collect_vec();
 
-    let mut p2_grid = p1_grid
+    let p2_grid = p1_grid
         .iter()
         .map(|row| {
             row.iter()
@@ -58,19 +97,23 @@ pub(crate) fn solve(input: &str) -> DayResult {
         })
         .collect_vec();
 
-    Ok((
-        part1(&mut p1_grid, &moves, robot_pos)?,
-        part2(&mut p2_grid, &moves, robot_pos)?,
-    ))
+    Ok(Warehouse {
+        p1_grid,
+        p2_grid,
+        moves,
+        robot_initial_pos: robot_pos,
+    })
 }
 
-fn part1(grid: &mut Vec<Vec<Tile>>, moves: &[Direction], robot_initial_pos: Pos) -> PartResult {
-    let mut robot_pos = robot_initial_pos;
+pub(crate) fn part1(warehouse: &Warehouse) -> PartResult {
+    // The boxes are pushed around as we go, so we need our own copy of the warehouse to do so in.
+    let mut grid = warehouse.p1_grid.clone();
+    let mut robot_pos = warehouse.robot_initial_pos;
 
-    for &move_dir in moves {
+    for &move_dir in &warehouse.moves {
         let target = robot_pos + move_dir;
 
-        // debug_show_state(grid, robot_pos);
+        // debug_show_state(&grid, robot_pos);
         // println!("Next Move: {:?}", move_dir);
         // wait_for_user();
 
@@ -86,7 +129,7 @@ fn part1(grid: &mut Vec<Vec<Tile>>, moves: &[Direction], robot_initial_pos: Pos)
         }
 
         // 'ight, we've dealt with the easy paths, now we just care about the box moving logic.
-        let Some(free_pos) = find_air_in_direction(grid, target, move_dir) else {
+        let Some(free_pos) = find_air_in_direction(&grid, target, move_dir) else {
             continue;
         };
 
@@ -113,10 +156,15 @@ fn part1(grid: &mut Vec<Vec<Tile>>, moves: &[Direction], robot_initial_pos: Pos)
     Ok(gps_sum.to_string())
 }
 
-fn part2(grid: &mut Vec<Vec<DWTile>>, moves: &[Direction], robot_initial_pos: Pos) -> PartResult {
-    let mut robot_pos = robot_initial_pos;
+pub(crate) fn part2(warehouse: &Warehouse) -> PartResult {
+    let mut grid = warehouse.p2_grid.clone();
+    // Everything is twice as wide, including how far the robot starts from the left wall.
+    let mut robot_pos = Pos {
+        x: warehouse.robot_initial_pos.x * 2,
+        ..warehouse.robot_initial_pos
+    };
 
-    'moves_loop: for &move_dir in moves {
+    'moves_loop: for &move_dir in &warehouse.moves {
         let target = robot_pos + move_dir;
         let target_tile = *grid.get_2d_unchecked(target);
 
@@ -133,7 +181,7 @@ fn part2(grid: &mut Vec<Vec<DWTile>>, moves: &[Direction], robot_initial_pos: Po
 
         if move_dir.is_horizontal() {
             // we've gotta actually move the whole row across this time!
-            let Some(free) = p2_find_air_horizontal(grid, target, move_dir) else {
+            let Some(free) = p2_find_air_horizontal(&grid, target, move_dir) else {
                 continue;
             };
 
@@ -1,26 +1,77 @@
+use indoc::indoc;
 use itertools::Itertools;
 use rustc_hash::FxHashSet;
 
 use crate::utils::{
-    boxdraw,
-    direction::{Direction, DIRECTIONS},
+    direction::Direction,
     not_yet_implemented,
     pos::{Index2d, Pos},
-    wait_for_user,
 };
 
-use super::{DayResult, PartResult};
+use super::{Example, ParseResult, PartResult};
 
-// TODO: Get back to this one! This is hard!
-pub(crate) fn solve(input: &str) -> DayResult {
-    Ok((part1(input)?, part2(input)?))
+day!(16, "Reindeer Maze");
+
+/// The maze, and where the reindeer starts and needs to get to.
+pub(crate) struct Maze {
+    grid: Grid,
+    start_pos: Pos,
+    exit_pos: Pos,
 }
 
-fn part1(input: &str) -> PartResult {
+pub(crate) const EXAMPLES: &[Example] = &[
+    Example::new(
+        indoc! {"
+            ###############
+            #.......#....E#
+            #.#.###.#.###.#
+            #.....#.#...#.#
+            #.###.#####.#.#
+            #.#.#.......#.#
+            #.#.#####.###.#
+            #...........#.#
+            ###.#.#####.#.#
+            #...#.....#.#.#
+            #.#.#.###.#.#.#
+            #.....#...#.#.#
+            #.###.#.#.#.#.#
+            #S..#.....#...#
+            ###############
+        "},
+        Some("7036"),
+        Some("45"),
+    ),
+    Example::new(
+        indoc! {"
+            #################
+            #...#...#...#..E#
+            #.#.#.#.#.#.#.#.#
+            #.#.#.#...#...#.#
+            #.#.#.#.###.#.#.#
+            #...#.#.#.....#.#
+            #.#.#.#.#.#####.#
+            #.#...#.#.#.....#
+            #.#.#####.#.###.#
+            #.#.#.......#...#
+            #.#.###.#####.###
+            #.#.#...#.....#.#
+            #.#.#.#####.###.#
+            #.#.#.........#.#
+            #.#.#.#########.#
+            #S#.............#
+            #################
+        "},
+        Some("11048"),
+        Some("64"),
+    ),
+];
+
+// TODO: Get back to this one! This is hard!
+pub(crate) fn parse(input: &str) -> ParseResult<Maze> {
     let mut start_pos = Pos::from(0);
     let mut exit_pos = Pos::from(0);
 
-    let mut grid = input
+    let grid = input
         .lines()
         .enumerate()
         .map(|(y, line)| {
@@ -43,19 +94,34 @@ fn part1(input: &str) -> PartResult {
         })
         .collect_vec();
 
-    let result = path_find(&mut grid, start_pos, Direction::Right, exit_pos)
+    Ok(Maze {
+        grid,
+        start_pos,
+        exit_pos,
+    })
+}
+
+pub(crate) fn part1(maze: &Maze) -> PartResult {
+    // Pathfinding records the cheapest route to each tile in the grid, so needs a fresh copy.
+    let mut grid = maze.grid.clone();
+
+    let result = path_find(&mut grid, maze.start_pos, Direction::Right, maze.exit_pos)
         .ok_or(anyhow::anyhow!("").into())
         .map(|dist| dist.to_string());
 
     result
 }
 
-fn part2(_input: &str) -> PartResult {
+pub(crate) fn part2(_maze: &Maze) -> PartResult {
     not_yet_implemented()
 }
 
+/// Grid of tiles, where walls are `None`, and open tiles hold the direction and cost of the cheapest
+/// route found to them so far (if any).
+type Grid = Vec<Vec<Option<Option<(Direction, u32)>>>>;
+
 fn path_find(
-    grid: &mut Vec<Vec<Option<Option<(Direction, u32)>>>>,
+    grid: &mut Grid,
     source: Pos,
     start_dir: Direction,
     destination: Pos,
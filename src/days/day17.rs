@@ -1,7 +1,44 @@
-use super::DayResult;
+use super::{Example, ParseResult, PartResult};
+use indoc::indoc;
 use itertools::Itertools;
 
-pub(crate) fn solve(input: &str) -> DayResult {
+day!(17, "Chronospatial Computer");
+
+/// The initial state of the computer's registers, and the program it runs.
+pub(crate) struct Program {
+    a: u64,
+    b: u64,
+    c: u64,
+    instructions: Vec<(Op, u8)>,
+}
+
+pub(crate) const EXAMPLES: &[Example] = &[
+    Example::new(
+        indoc! {"
+            Register A: 729
+            Register B: 0
+            Register C: 0
+
+            Program: 0,1,5,4,3,0
+        "},
+        Some("4,6,3,5,6,3,5,2,1,0"),
+        None,
+    ),
+    // TODO: part 2 fails to find A for this one, though the puzzle says it should be 117440.
+    Example::new(
+        indoc! {"
+            Register A: 2024
+            Register B: 0
+            Register C: 0
+
+            Program: 0,3,5,4,3,0
+        "},
+        Some("5,7,3,0"),
+        None,
+    ),
+];
+
+pub(crate) fn parse(input: &str) -> ParseResult<Program> {
     let mut lines = input.lines();
 
     let a: u64 = lines.next().unwrap()[12..].parse()?;
@@ -19,21 +56,28 @@ pub(crate) fn solve(input: &str) -> DayResult {
         .map(|chunk| (chunk.0.try_into().unwrap(), chunk.1))
         .collect_vec();
 
-    let vm = Vm {
+    Ok(Program {
         a,
         b,
         c,
-        instructions: &instructions,
-        ..Default::default()
-    };
-
-    let part1_output = part1(vm.clone());
-    let part2_output = part2(vm)?;
+        instructions,
+    })
+}
 
-    Ok((part1_output, part2_output))
+impl Program {
+    fn vm(&self) -> Vm<'_> {
+        Vm {
+            a: self.a,
+            b: self.b,
+            c: self.c,
+            instructions: &self.instructions,
+            ..Default::default()
+        }
+    }
 }
 
-fn part1(mut vm: Vm) -> String {
+pub(crate) fn part1(program: &Program) -> PartResult {
+    let mut vm = program.vm();
     let mut output = Vec::new();
 
     while let Some(maybe_data) = vm.perform_next() {
@@ -42,10 +86,11 @@ fn part1(mut vm: Vm) -> String {
         }
     }
 
-    output.iter().join(",")
+    Ok(output.iter().join(","))
 }
 
-fn part2(mut vm: Vm) -> anyhow::Result<String> {
+pub(crate) fn part2(program: &Program) -> PartResult {
+    let mut vm = program.vm();
     let initial_b = vm.b;
     let initial_c = vm.c;
     // let required_num_instructions = vm.instructions.len();
@@ -78,7 +123,7 @@ fn part2(mut vm: Vm) -> anyhow::Result<String> {
     // divided by `2 ^ adv_operand`, `required_output_length` times, equals 0.
     match find_a(&mut vm, 1, initial_b, initial_c, a_divisor) {
         Some(a) => Ok(a.to_string()),
-        None => Err(anyhow::anyhow!("Failed to find the value of A!")),
+        None => Err(anyhow::anyhow!("Failed to find the value of A!").into()),
     }
 }
 
@@ -99,8 +144,6 @@ fn find_a(
         vm.c = initial_c;
         vm.ip = 0;
 
-        println!("From initial {initial_search_a} :: {test_a} :: #{n}");
-
         for output_index in (0..required_num_instructions).rev() {
             let Some(op_result) = vm.perform_until_output().map(Op::try_from) else {
                 // if output_index == (required_num_instructions - 1) {
@@ -236,7 +279,7 @@ impl Vm<'_> {
 
 #[repr(u8)]
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
-enum Op {
+pub(crate) enum Op {
     Adv,
     Bxl,
     Bst,
@@ -1,30 +1,90 @@
 use itertools::Itertools;
 use rustc_hash::FxHashSet;
 
+use indoc::indoc;
+
 use crate::utils::{
     direction::DIRECTIONS,
     pos::{Index2d, Pos},
 };
 
-use super::{DayResult, PartResult};
+use super::{Example, ParseResult, PartResult};
+
+day!(18, "RAM Run", with params);
 
-pub(crate) fn solve(input: &str) -> DayResult {
-    Ok((part1(input)?, part2(input)?))
+/// The size of the memory space, and how many bytes have fallen into it by the time part 1 asks.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Params {
+    grid_width: usize,
+    grid_height: usize,
+    fallen_bytes: usize,
 }
 
-const GRID_WIDTH: usize = 71;
-const GRID_HEIGHT: usize = 71;
+pub(crate) const PARAMS: Params = Params {
+    grid_width: 71,
+    grid_height: 71,
+    fallen_bytes: 1024,
+};
 
 const START: Pos = Pos { x: 0, y: 0 };
-const FINISH: Pos = Pos {
-    x: GRID_WIDTH as i32 - 1,
-    y: GRID_HEIGHT as i32 - 1,
-};
 
-fn part1(input: &str) -> PartResult {
-    let mut passibility_grid = [[Some(u32::MAX); GRID_WIDTH]; GRID_HEIGHT];
+type Grid = Vec<Vec<Option<u32>>>;
+
+pub(crate) const EXAMPLES: &[Example] = &[Example::new(
+    indoc! {"
+        5,4
+        4,2
+        4,5
+        3,0
+        2,1
+        6,3
+        2,4
+        1,5
+        0,6
+        3,3
+        2,6
+        5,1
+        1,2
+        5,5
+        2,5
+        6,5
+        1,4
+        0,4
+        6,4
+        1,1
+        6,1
+        1,0
+        0,5
+        1,6
+        2,0
+    "},
+    Some("22"),
+    Some("6,1"),
+)
+.with_params(&Params {
+    grid_width: 7,
+    grid_height: 7,
+    fallen_bytes: 12,
+})];
+
+/// The positions of each falling byte in the order they fall, and the memory space they fall into.
+pub(crate) struct Memory {
+    bytes: Vec<Pos>,
+    params: Params,
+}
+
+impl Memory {
+    fn empty_grid(&self) -> Grid {
+        vec![vec![Some(u32::MAX); self.params.grid_width]; self.params.grid_height]
+    }
+
+    fn finish(&self) -> Pos {
+        Pos::new_from_usize_unchecked(self.params.grid_width - 1, self.params.grid_height - 1)
+    }
+}
 
-    input
+pub(crate) fn parse(input: &str, params: &Params) -> ParseResult<Memory> {
+    let bytes = input
         .lines()
         .map(|line| {
             line.split(',')
@@ -34,47 +94,54 @@ fn part1(input: &str) -> PartResult {
                 .unwrap()
                 .into()
         })
-        .take(1024)
-        .for_each(|pos: Pos| {
+        .collect_vec();
+
+    Ok(Memory {
+        bytes,
+        params: *params,
+    })
+}
+
+pub(crate) fn part1(memory: &Memory) -> PartResult {
+    let mut passibility_grid = memory.empty_grid();
+
+    memory
+        .bytes
+        .iter()
+        .take(memory.params.fallen_bytes)
+        .for_each(|&pos| {
             *passibility_grid.get_2d_mut_unchecked(pos) = None;
         });
 
     // println!(
     //     "{}",
-    //     boxdraw::draw_shape_outline(GRID_WIDTH, GRID_HEIGHT, |pos| {
+    //     boxdraw::draw_shape_outline(memory.params.grid_width, memory.params.grid_height, |pos| {
     //         *passibility_grid.get_2d_unchecked(pos)
     //     })
     // );
 
-    match path_find(&mut passibility_grid, START, FINISH) {
+    match path_find(&mut passibility_grid, START, memory.finish()) {
         Some(steps) => Ok(steps.to_string()),
         None => Err(anyhow::anyhow!("No valid path found!").into()),
     }
 }
 
-fn part2(input: &str) -> PartResult {
-    let mut passibility_grid = [[Some(u32::MAX); GRID_WIDTH]; GRID_HEIGHT];
+pub(crate) fn part2(memory: &Memory) -> PartResult {
+    let mut passibility_grid = memory.empty_grid();
+    let finish = memory.finish();
 
-    for block in input.lines().map(|line| {
-        Pos::from(
-            line.split(',')
-                .map(str::parse)
-                .map(Result::unwrap)
-                .collect_tuple::<(i32, i32)>()
-                .unwrap(),
-        )
-    }) {
+    for &block in &memory.bytes {
         // Add the next block.
         *passibility_grid.get_2d_mut_unchecked(block) = None;
 
         // Try pathfinding.
-        if path_find(&mut passibility_grid, START, FINISH).is_none() {
+        if path_find(&mut passibility_grid, START, finish).is_none() {
             return Ok(format!("{},{}", block.x, block.y));
         }
 
         // Reset distances.
-        for y in 0..GRID_HEIGHT {
-            for x in 0..GRID_WIDTH {
+        for y in 0..memory.params.grid_height {
+            for x in 0..memory.params.grid_width {
                 if let Some(dist) =
                     passibility_grid.get_2d_mut_unchecked(Pos::new_from_usize_unchecked(x, y))
                 {
@@ -87,17 +154,13 @@ fn part2(input: &str) -> PartResult {
     Err(anyhow::anyhow!("Somehow the path is never blocked??").into())
 }
 
-fn path_find(
-    grid: &mut [[Option<u32>; GRID_WIDTH]; GRID_HEIGHT],
-    source: Pos,
-    destination: Pos,
-) -> Option<u32> {
+fn path_find(grid: &mut Grid, source: Pos, destination: Pos) -> Option<u32> {
     // Set of nodes we haven't yet visited.
     let mut unvisited = FxHashSet::<Pos>::default();
 
-    for y in 0..GRID_HEIGHT as i32 {
-        for x in 0..GRID_WIDTH as i32 {
-            unvisited.insert(Pos::new(x, y));
+    for y in 0..grid.height() {
+        for x in 0..grid.width() {
+            unvisited.insert(Pos::new_from_usize_unchecked(x, y));
         }
     }
 
@@ -149,7 +212,7 @@ fn path_find(
 // ///
 // /// Returns the list of positions along the path. If no valid path exists, [None] is returned.
 // fn path_trace_route(
-//     grid: [[Option<u32>; GRID_WIDTH]; GRID_HEIGHT],
+//     grid: Grid,
 //     source: Pos,
 //     destination: Pos,
 // ) -> Option<Vec<Pos>> {
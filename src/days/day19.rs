@@ -1,15 +1,36 @@
+use indoc::indoc;
+
 use crate::utils::not_yet_implemented;
 
-use super::{DayResult, PartResult};
+use super::{Example, ParseResult, PartResult};
+
+day!(19, "Linen Layout");
+
+pub(crate) const EXAMPLES: &[Example] = &[Example::new(
+    indoc! {"
+        r, wr, b, g, bwu, rb, gb, br
+
+        brwrr
+        bggr
+        gbbr
+        rrbgbr
+        ubwu
+        bwurrg
+        brgr
+        bbrgwb
+    "},
+    Some("6"),
+    Some("16"),
+)];
 
-pub(crate) fn solve(input: &str) -> DayResult {
-    Ok((part1(input)?, part2(input)?))
+pub(crate) fn parse(input: &str) -> ParseResult<String> {
+    Ok(input.to_string())
 }
 
-fn part1(input: &str) -> PartResult {
+pub(crate) fn part1(_input: &str) -> PartResult {
     not_yet_implemented()
 }
 
-fn part2(input: &str) -> PartResult {
+pub(crate) fn part2(_input: &str) -> PartResult {
     not_yet_implemented()
 }
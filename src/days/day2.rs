@@ -1,41 +1,55 @@
-use super::{DayResult, PartResult};
+use indoc::indoc;
+
+use super::{Example, ParseResult, PartResult};
+
+day!(2, "Red-Nosed Reports");
 
 const MIN_DEVIATION: i32 = 1;
 const MAX_DEVIATION: i32 = 3;
 
-pub(crate) fn solve(input: &str) -> DayResult {
-    Ok((part1(input)?, part2(input)?))
-}
-
-fn part1(input: &str) -> PartResult {
-    let num_reports_ok = input
+pub(crate) const EXAMPLES: &[Example] = &[Example::new(
+    indoc! {"
+        7 6 4 2 1
+        1 2 7 8 9
+        9 7 6 2 1
+        1 3 2 4 5
+        8 6 4 4 1
+        1 3 6 7 9
+    "},
+    Some("2"),
+    Some("4"),
+)];
+
+pub(crate) fn parse(input: &str) -> ParseResult<Vec<Vec<i32>>> {
+    Ok(input
         .lines()
         .map(str::split_whitespace)
         .map(|line| {
             line.map(|num| num.parse::<i32>().expect("Invalid number in line!"))
                 .collect::<Vec<i32>>()
         })
+        .collect())
+}
+
+pub(crate) fn part1(reports: &[Vec<i32>]) -> PartResult {
+    let num_reports_ok = reports
+        .iter()
         .filter(|report| evaluate_report(report))
         .count();
 
     Ok(num_reports_ok.to_string())
 }
 
-fn part2(input: &str) -> PartResult {
-    let num_reports_ok = input
-        .lines()
-        .map(str::split_whitespace)
-        .map(|line| {
-            line.map(|num| num.parse::<i32>().expect("Invalid number in line!"))
-                .collect::<Vec<i32>>()
-        })
+pub(crate) fn part2(reports: &[Vec<i32>]) -> PartResult {
+    let num_reports_ok = reports
+        .iter()
         .filter(|report| {
             if evaluate_report(report) {
                 return true;
             }
 
             // fuck it, brute force it, i'm quite lost lol.
-            let mut mutable_report = report.clone();
+            let mut mutable_report = report.to_vec();
 
             for i in 0..report.len() {
                 let entry = mutable_report.remove(i);
@@ -1,18 +1,23 @@
-use super::{DayResult, PartResult};
+use super::{Example, ParseResult, PartResult};
 
-pub(crate) fn solve(input: &str) -> DayResult {
-    Ok((part1(input)?, part2(input)?))
+day!(3, "Mull It Over");
+
+pub(crate) const EXAMPLES: &[Example] = &[Example::new(
+    "xmul(2,4)&mul[3,7]!^don't()_mul(5,5)+mul(32,64](mul(11,8)undo()?mul(8,5))",
+    Some("161"),
+    Some("48"),
+)];
+
+/// The instructions are scanned for straight from the corrupted memory, so there's nothing to parse.
+pub(crate) fn parse(input: &str) -> ParseResult<String> {
+    Ok(input.to_string())
 }
 
-fn part1(input: &str) -> PartResult {
+pub(crate) fn part1(input: &str) -> PartResult {
     let mut rem_input = input;
     let mut sum = 0;
 
-    loop {
-        let Some(next_match) = rem_input.find("mul(") else {
-            break;
-        };
-
+    while let Some(next_match) = rem_input.find("mul(") {
         rem_input = &rem_input[next_match + 4..];
 
         let Some(comma) = rem_input.find(",") else {
@@ -41,16 +46,12 @@ fn part1(input: &str) -> PartResult {
     Ok(sum.to_string())
 }
 
-fn part2(input: &str) -> PartResult {
+pub(crate) fn part2(input: &str) -> PartResult {
     let mut rem_input = input;
     let mut bother = true;
     let mut sum = 0;
 
-    loop {
-        let Some(next_match) = rem_input.find("mul(") else {
-            break;
-        };
-
+    while let Some(next_match) = rem_input.find("mul(") {
         match bother {
             true => {
                 if let Some(next_dont) = rem_input.find("don't()") {
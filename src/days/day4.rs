@@ -1,4 +1,8 @@
-use super::{DayResult, PartResult};
+use indoc::indoc;
+
+use super::{Example, ParseResult, PartResult};
+
+day!(4, "Ceres Search");
 
 const XMAS: &[char] = &['X', 'M', 'A', 'S'];
 
@@ -16,16 +20,31 @@ const P2_CHECK_OFFSETS: &[(i32, i32)] = &[
 	(-1,  1),				(1,  1),
 ];
 
-pub(crate) fn solve(input: &str) -> DayResult {
-    let char_matrix = input
+pub(crate) const EXAMPLES: &[Example] = &[Example::new(
+    indoc! {"
+        MMMSXXMASM
+        MSAMXMSMSA
+        AMXSXMAAMM
+        MSAMASMSMX
+        XMASAMXAMM
+        XXAMMXXAMA
+        SMSMSASXSS
+        SAXAMASAAA
+        MAMMMXMMMM
+        MXMXAXMASX
+    "},
+    Some("18"),
+    Some("9"),
+)];
+
+pub(crate) fn parse(input: &str) -> ParseResult<Vec<Vec<char>>> {
+    Ok(input
         .lines()
         .map(|line| line.chars().collect())
-        .collect::<Vec<Vec<char>>>();
-
-    Ok((part1(&char_matrix)?, part2(&char_matrix)?))
+        .collect::<Vec<Vec<char>>>())
 }
 
-fn part1(char_matrix: &[Vec<char>]) -> PartResult {
+pub(crate) fn part1(char_matrix: &[Vec<char>]) -> PartResult {
     let mut matches: usize = 0;
 
     // 1. Iterate over line, and each character.
@@ -69,7 +88,7 @@ fn part1(char_matrix: &[Vec<char>]) -> PartResult {
     Ok(matches.to_string())
 }
 
-fn part2(char_matrix: &[Vec<char>]) -> PartResult {
+pub(crate) fn part2(char_matrix: &[Vec<char>]) -> PartResult {
     let mut matches: usize = 0;
 
     for y in 1..char_matrix.len() - 1 {
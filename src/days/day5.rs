@@ -1,9 +1,50 @@
+use indoc::indoc;
 use rustc_hash::FxHashMap;
 use std::cmp::Ordering;
 
-use super::{DayResult, PartResult};
-
-pub(crate) fn solve(input: &str) -> DayResult {
+use super::{Example, ParseResult, PartResult};
+
+day!(5, "Print Queue");
+
+/// Each page's dependencies, and the list of updates.
+type Parsed = (FxHashMap<usize, Vec<usize>>, Vec<Vec<usize>>);
+
+pub(crate) const EXAMPLES: &[Example] = &[Example::new(
+    indoc! {"
+        47|53
+        97|13
+        97|61
+        97|47
+        75|29
+        61|13
+        75|53
+        29|13
+        97|29
+        53|29
+        61|53
+        97|53
+        61|29
+        47|13
+        75|47
+        97|75
+        47|61
+        75|61
+        47|29
+        75|13
+        53|13
+
+        75,47,61,53,29
+        97,61,53,29,13
+        75,29,13
+        75,97,47,61,53
+        61,13,29
+        97,13,75,29,47
+    "},
+    Some("143"),
+    Some("123"),
+)];
+
+pub(crate) fn parse(input: &str) -> ParseResult<Parsed> {
     let mut lines = input.lines();
     let mut dependencies = FxHashMap::<usize, Vec<usize>>::default();
 
@@ -33,13 +74,10 @@ pub(crate) fn solve(input: &str) -> DayResult {
         })
         .collect::<Vec<Vec<usize>>>();
 
-    Ok((
-        part1(&dependencies, &updates)?,
-        part2(&dependencies, &updates)?,
-    ))
+    Ok((dependencies, updates))
 }
 
-fn part1(dependencies: &FxHashMap<usize, Vec<usize>>, updates: &[Vec<usize>]) -> PartResult {
+pub(crate) fn part1((dependencies, updates): &Parsed) -> PartResult {
     let correct = updates
         .iter()
         .filter(|update| is_sorted(update, dependencies));
@@ -48,7 +86,7 @@ fn part1(dependencies: &FxHashMap<usize, Vec<usize>>, updates: &[Vec<usize>]) ->
     Ok(sum_middle_pages.to_string())
 }
 
-fn part2(dependencies: &FxHashMap<usize, Vec<usize>>, updates: &[Vec<usize>]) -> PartResult {
+pub(crate) fn part2((dependencies, updates): &Parsed) -> PartResult {
     // We're working under the assumption that there IS always a valid order, and work to achieve
     // that.
 
@@ -3,11 +3,31 @@ use crate::utils::{
     pos::{FlatIndex, FlatIndexMut, Pos},
 };
 
-use super::{DayResult, PartResult};
+use super::{Example, ParseResult, PartResult};
 use enumflags2::BitFlags;
+use indoc::indoc;
 use rayon::iter::{IndexedParallelIterator, IntoParallelRefIterator, ParallelIterator};
 
-pub(crate) fn solve(input: &str) -> DayResult {
+day!(6, "Guard Gallivant");
+
+pub(crate) const EXAMPLES: &[Example] = &[Example::new(
+    indoc! {"
+        ....#.....
+        .........#
+        ..........
+        ..#.......
+        .......#..
+        ..........
+        .#..^.....
+        ........#.
+        #.........
+        ......#...
+    "},
+    Some("41"),
+    Some("6"),
+)];
+
+pub(crate) fn parse(input: &str) -> ParseResult<Lab> {
     let mut grid = input
         .lines()
         .flat_map(|line| line.bytes())
@@ -33,23 +53,37 @@ pub(crate) fn solve(input: &str) -> DayResult {
 
     *grid.flat_index_mut(grid_width, initial_pos) = FLOOR;
 
-    Ok((
-        part1(&grid, grid_width, grid_height, initial_pos, initial_dir)?,
-        part2(&mut grid, grid_width, grid_height, initial_pos, initial_dir)?,
-    ))
+    Ok(Lab {
+        grid,
+        grid_width,
+        grid_height,
+        initial_pos,
+        initial_dir,
+    })
 }
 
-const FLOOR: u8 = b'.';
-const WALL: u8 = b'#';
-const GUARD_INITIAL: u8 = b'^';
-
-fn part1(
-    grid: &[u8],
+/// The lab's floor plan, and where the guard starts out in it.
+pub(crate) struct Lab {
+    grid: Vec<u8>,
     grid_width: usize,
     grid_height: usize,
     initial_pos: Pos,
     initial_dir: Direction,
-) -> PartResult {
+}
+
+const FLOOR: u8 = b'.';
+const WALL: u8 = b'#';
+const GUARD_INITIAL: u8 = b'^';
+
+pub(crate) fn part1(lab: &Lab) -> PartResult {
+    let &Lab {
+        ref grid,
+        grid_width,
+        grid_height,
+        initial_pos,
+        initial_dir,
+    } = lab;
+
     let mut visit_grid = vec![BitFlags::<Direction>::empty(); grid_width * grid_height];
     let visited = trace_path(
         grid,
@@ -65,13 +99,15 @@ fn part1(
     Ok(visited.to_string())
 }
 
-fn part2(
-    grid: &mut [u8],
-    grid_width: usize,
-    grid_height: usize,
-    initial_pos: Pos,
-    initial_dir: Direction,
-) -> PartResult {
+pub(crate) fn part2(lab: &Lab) -> PartResult {
+    let &Lab {
+        ref grid,
+        grid_width,
+        grid_height,
+        initial_pos,
+        initial_dir,
+    } = lab;
+
     // 1. Let's map out their path as in part 1.
     // 2. For each visited position, try placing a wall there.
     // 3. Record the pathfinding, rather than an X, store direction (bitwise mayhaps :P)
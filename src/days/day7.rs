@@ -1,12 +1,31 @@
+use indoc::indoc;
 use itertools::Itertools;
 use rayon::{
     iter::{IntoParallelRefIterator, ParallelIterator},
     str::ParallelString,
 };
 
-use super::{DayResult, PartResult};
-
-pub(crate) fn solve(input: &str) -> DayResult {
+use super::{Example, ParseResult, PartResult};
+
+day!(7, "Bridge Repair");
+
+pub(crate) const EXAMPLES: &[Example] = &[Example::new(
+    indoc! {"
+        190: 10 19
+        3267: 81 40 27
+        83: 17 5
+        156: 15 6
+        7290: 6 8 6 15
+        161011: 16 10 13
+        192: 17 8 14
+        21037: 9 7 18 13
+        292: 11 6 16 20
+    "},
+    Some("3749"),
+    Some("11387"),
+)];
+
+pub(crate) fn parse(input: &str) -> ParseResult<Vec<Equation>> {
     let equations: Vec<Equation> = input
         .par_lines()
         .map(|line| {
@@ -19,10 +38,10 @@ pub(crate) fn solve(input: &str) -> DayResult {
         })
         .collect();
 
-    Ok((part1(&equations)?, part2(&equations)?))
+    Ok(equations)
 }
 
-fn part1(equations: &[Equation]) -> PartResult {
+pub(crate) fn part1(equations: &[Equation]) -> PartResult {
     let sum: usize = equations
         .par_iter()
         .filter(|equation| is_possible(equation.result, &equation.operands, &[Op::Add, Op::Mul]))
@@ -32,7 +51,7 @@ fn part1(equations: &[Equation]) -> PartResult {
     Ok(sum.to_string())
 }
 
-fn part2(equations: &[Equation]) -> PartResult {
+pub(crate) fn part2(equations: &[Equation]) -> PartResult {
     let sum: usize = equations
         .par_iter()
         .filter(|equation| {
@@ -67,7 +86,7 @@ fn is_possible(result: usize, operands: &[usize], ops: &[Op]) -> bool {
 
                 // For multiplication, `x * last == result`, thus `result / last == x`.
                 // `x` must be a valid integer (`result % last == 0`)
-                Op::Mul => (result % last == 0) && is_possible(result / last, remaining, ops),
+                Op::Mul => result.is_multiple_of(*last) && is_possible(result / last, remaining, ops),
 
                 // Concatenation means that `result` ends in `last`, and `x` is the rest of
                 // `result`'s digits.
@@ -83,7 +102,7 @@ fn is_possible(result: usize, operands: &[usize], ops: &[Op]) -> bool {
                     let divisor = 10_usize.pow(last.ilog10() + 1);
                     let result_removed_last = result - last;
 
-                    (result_removed_last % divisor == 0)
+                    result_removed_last.is_multiple_of(divisor)
                         && is_possible(result_removed_last / divisor, remaining, ops)
                 }
             }
@@ -92,7 +111,7 @@ fn is_possible(result: usize, operands: &[usize], ops: &[Op]) -> bool {
     }
 }
 
-struct Equation {
+pub(crate) struct Equation {
     result: usize,
     operands: Vec<usize>,
 }
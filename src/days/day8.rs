@@ -1,10 +1,39 @@
 use self::iter::MoreIterTools;
-use super::DayResult;
+use super::{Example, ParseResult, PartResult};
 use crate::utils::iter;
 use crate::utils::pos::Pos;
-use rustc_hash::FxHashSet;
+use indoc::indoc;
+use rustc_hash::{FxHashMap, FxHashSet};
 
-pub(crate) fn solve(input: &str) -> DayResult {
+day!(8, "Resonant Collinearity");
+
+/// The positions of every antenna, grouped by frequency, within the bounds of the map.
+pub(crate) struct Antennas {
+    antenna_types: FxHashMap<char, Vec<Pos>>,
+    grid_width: usize,
+    grid_height: usize,
+}
+
+pub(crate) const EXAMPLES: &[Example] = &[Example::new(
+    indoc! {"
+        ............
+        ........0...
+        .....0......
+        .......0....
+        ....0.......
+        ......A.....
+        ............
+        ............
+        ........A...
+        .........A..
+        ............
+        ............
+    "},
+    Some("14"),
+    Some("34"),
+)];
+
+pub(crate) fn parse(input: &str) -> ParseResult<Antennas> {
     let grid_width = input.lines().next().unwrap().len();
     let grid_height = input.lines().count();
 
@@ -27,6 +56,20 @@ pub(crate) fn solve(input: &str) -> DayResult {
         })
         .into_group_map_fx();
 
+    Ok(Antennas {
+        antenna_types,
+        grid_width,
+        grid_height,
+    })
+}
+
+pub(crate) fn part1(antennas: &Antennas) -> PartResult {
+    let &Antennas {
+        ref antenna_types,
+        grid_width,
+        grid_height,
+    } = antennas;
+
     let mut antinodes = FxHashSet::<Pos>::default();
 
     for antennas in antenna_types.values() {
@@ -45,7 +88,17 @@ pub(crate) fn solve(input: &str) -> DayResult {
         }
     }
 
-    let p1_num_antinodes = antinodes.len();
+    Ok(antinodes.len().to_string())
+}
+
+pub(crate) fn part2(antennas: &Antennas) -> PartResult {
+    let &Antennas {
+        ref antenna_types,
+        grid_width,
+        grid_height,
+    } = antennas;
+
+    let mut antinodes = FxHashSet::<Pos>::default();
 
     for antennas in antenna_types.values() {
         for i in 0..antennas.len() {
@@ -68,7 +121,5 @@ pub(crate) fn solve(input: &str) -> DayResult {
         }
     }
 
-    let p2_num_antinodes = antinodes.len();
-
-    Ok((p1_num_antinodes.to_string(), p2_num_antinodes.to_string()))
+    Ok(antinodes.len().to_string())
 }
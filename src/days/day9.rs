@@ -1,9 +1,17 @@
 
-use super::{DayResult, PartResult};
+use super::{Example, ParseResult, PartResult};
 use itertools::Itertools;
 use rustc_hash::FxHashSet;
 
-pub(crate) fn solve(input: &str) -> DayResult {
+day!(9, "Disk Fragmenter");
+
+pub(crate) const EXAMPLES: &[Example] = &[Example::new(
+    "2333133121414131402",
+    Some("1928"),
+    Some("2858"),
+)];
+
+pub(crate) fn parse(input: &str) -> ParseResult<Vec<usize>> {
     let fs = input
         .lines()
         .next()
@@ -11,10 +19,10 @@ pub(crate) fn solve(input: &str) -> DayResult {
         .chars()
         .map(|char| char.to_digit(10).unwrap() as usize)
         .collect_vec();
-    Ok((part1(&fs)?, part2(input)?))
+    Ok(fs)
 }
 
-fn part1(fs: &[usize]) -> PartResult {
+pub(crate) fn part1(fs: &[usize]) -> PartResult {
     // 1. Iterate forward and backwards over the FS blocks.
     // 2. Pour the block's contents into the free block. If it runs out of space, move to the next
     //    free block.
@@ -25,7 +33,7 @@ fn part1(fs: &[usize]) -> PartResult {
     let mut free_index: usize = 1;
     let mut block_index: usize = fs.len() - 1;
 
-    if block_index % 2 != 0 {
+    if !block_index.is_multiple_of(2) {
         block_index -= 1;
     }
 
@@ -88,13 +96,10 @@ fn part1(fs: &[usize]) -> PartResult {
     Ok(checksum.to_string())
 }
 
-fn part2(input: &str) -> PartResult {
-    let mut fs: Vec<Block> = input
-        .lines()
-        .next()
-        .unwrap()
-        .chars()
-        .map(|char| char.to_digit(10).unwrap())
+pub(crate) fn part2(disk_map: &[usize]) -> PartResult {
+    let mut fs: Vec<Block> = disk_map
+        .iter()
+        .map(|&size| size as u32)
         .enumerate()
         .map(|(index, size)| match index % 2 {
             0 => Block::new_file(index / 2, size),
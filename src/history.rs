@@ -0,0 +1,315 @@
+//! A history of every run's results, kept between runs so that a change to a solution can be
+//! checked for having made it slower, or for having changed its answers.
+//!
+//! The history is a single file of JSON lines, with one line for each part of each day run, so
+//! that recording a run only ever needs to append to it. Only runs on the days' own inputs are
+//! recorded, as the timings and answers for anything else can't be compared.
+
+use std::{
+    fs::{File, OpenOptions},
+    io::{self, BufRead, BufReader, Write},
+    path::Path,
+    process::Command,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{days::Part, runner::DayRun};
+
+pub const HISTORY_FILE_PATH: &str = "history.jsonl";
+
+/// The threshold used by default for a part to count as having got slower, as a percentage.
+pub const DEFAULT_SLOWDOWN_THRESHOLD: f64 = 10.0;
+
+/// How much slower a part has to get to count as having got slower, whatever the percentage. Parts
+/// which only take microseconds vary by far more than the threshold from one run to the next.
+pub const MIN_SLOWDOWN: Duration = Duration::from_micros(100);
+
+/// The result of a single part of a day from a run, as kept in the history.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Entry {
+    /// The number of the run, counting up from 1 across the whole history.
+    pub run: u64,
+    /// When the run was made, in seconds since the Unix epoch.
+    pub timestamp: u64,
+    /// The git commit checked out at the time, if we could find out.
+    pub commit: Option<String>,
+    /// Whether there were uncommitted changes on top of the commit.
+    pub dirty: bool,
+    pub year: u16,
+    pub day: usize,
+    pub part: u8,
+    /// How long the part took. For benchmarks, this is the median.
+    pub median_us: u64,
+    /// How many timed runs the median was taken from.
+    pub samples: usize,
+    /// The part's answer, if it produced one.
+    pub answer: Option<String>,
+}
+
+impl Entry {
+    fn median(&self) -> Duration {
+        Duration::from_micros(self.median_us)
+    }
+}
+
+/// The result of a single part, ready to be recorded once the whole run is over.
+pub struct Measurement {
+    day: usize,
+    part: Part,
+    median: Duration,
+    samples: usize,
+    answer: Option<String>,
+}
+
+impl Measurement {
+    /// The measurements of each part run as part of running a day.
+    pub fn of_day(day: usize, run: &DayRun) -> impl Iterator<Item = Measurement> + '_ {
+        run.parts.iter().map(move |part_run| Measurement {
+            day,
+            part: part_run.part,
+            median: part_run.timing.elapsed(),
+            samples: part_run.timing.samples(),
+            answer: part_run.outcome.as_ref().ok().cloned(),
+        })
+    }
+}
+
+/// Append the measurements from a run to the history, as a new run. Returns the number given to
+/// the run.
+pub fn record_run(path: &Path, year: u16, measurements: &[Measurement]) -> Result<u64, io::Error> {
+    let run = load(path)?.last().map_or(1, |entry| entry.run + 1);
+    let timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |elapsed| elapsed.as_secs());
+    let (commit, dirty) =
+        current_commit().map_or((None, false), |(hash, dirty)| (Some(hash), dirty));
+
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    let mut lines = Vec::new();
+
+    for measurement in measurements {
+        let entry = Entry {
+            run,
+            timestamp,
+            commit: commit.clone(),
+            dirty,
+            year,
+            day: measurement.day,
+            part: measurement.part.number(),
+            median_us: measurement.median.as_micros() as u64,
+            samples: measurement.samples,
+            answer: measurement.answer.clone(),
+        };
+
+        serde_json::to_writer(&mut lines, &entry)?;
+        lines.push(b'\n');
+    }
+
+    // Written all at once, so that a run is never left half recorded.
+    file.write_all(&lines)?;
+
+    Ok(run)
+}
+
+/// Load every entry in the history, oldest first. A missing history is just an empty one.
+pub fn load(path: &Path) -> Result<Vec<Entry>, io::Error> {
+    let file = match File::open(path) {
+        Ok(file) => file,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(err) => return Err(err),
+    };
+
+    let mut entries = Vec::new();
+
+    for line in BufReader::new(file).lines() {
+        let line = line?;
+
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        entries.push(serde_json::from_str(&line)?);
+    }
+
+    Ok(entries)
+}
+
+/// The hash of the commit currently checked out, and whether there are changes on top of it.
+fn current_commit() -> Option<(String, bool)> {
+    let output = Command::new("git")
+        .args(["rev-parse", "HEAD"])
+        .output()
+        .ok()?;
+
+    if !output.status.success() {
+        return None;
+    }
+
+    let hash = String::from_utf8(output.stdout).ok()?.trim().to_string();
+
+    let dirty = Command::new("git")
+        .args(["status", "--porcelain", "--untracked-files=no"])
+        .output()
+        .is_ok_and(|output| !output.stdout.is_empty());
+
+    Some((hash, dirty))
+}
+
+/// All of the entries recorded by a single run.
+pub struct Run<'a> {
+    pub number: u64,
+    pub entries: Vec<&'a Entry>,
+}
+
+impl Run<'_> {
+    fn first(&self) -> &Entry {
+        self.entries[0]
+    }
+
+    /// A short description of when the run was made, and on which commit.
+    pub fn describe(&self) -> String {
+        let first = self.first();
+
+        let commit = match &first.commit {
+            Some(commit) => format!(
+                "{}{}",
+                &commit[..commit.len().min(7)],
+                if first.dirty { "+dirty" } else { "" }
+            ),
+            None => "unknown commit".to_string(),
+        };
+
+        let when =
+            humantime::format_rfc3339_seconds(UNIX_EPOCH + Duration::from_secs(first.timestamp));
+
+        format!("run {} ({}, {})", self.number, commit, when)
+    }
+}
+
+/// Split the history up into its runs, oldest first.
+pub fn runs(entries: &[Entry]) -> Vec<Run<'_>> {
+    entries
+        .chunk_by(|a, b| a.run == b.run)
+        .map(|entries| Run {
+            number: entries[0].run,
+            entries: entries.iter().collect(),
+        })
+        .collect()
+}
+
+/// Find a run from how the user referred to it: either its number, or a commit it was made at, in
+/// which case it's the latest run made there.
+pub fn find_run<'a, 'b>(runs: &'b [Run<'a>], reference: &str) -> Option<&'b Run<'a>> {
+    if let Ok(number) = reference.parse::<u64>() {
+        if let Some(run) = runs.iter().find(|run| run.number == number) {
+            return Some(run);
+        }
+    }
+
+    runs.iter().rev().find(|run| {
+        run.first()
+            .commit
+            .as_ref()
+            .is_some_and(|commit| commit.starts_with(reference))
+    })
+}
+
+/// How a part fared in one run compared to a baseline run.
+pub struct Comparison<'a> {
+    pub baseline: &'a Entry,
+    pub current: &'a Entry,
+}
+
+impl Comparison<'_> {
+    /// How much slower the part got, as a percentage. Negative if it got faster.
+    pub fn slowdown(&self) -> f64 {
+        let baseline = self.baseline.median().as_secs_f64();
+        let current = self.current.median().as_secs_f64();
+
+        if baseline == 0.0 {
+            return 0.0;
+        }
+
+        (current - baseline) / baseline * 100.0
+    }
+
+    /// Whether the part got slower by more than `threshold` percent, and by at least
+    /// [MIN_SLOWDOWN].
+    pub fn is_slower(&self, threshold: f64) -> bool {
+        self.slowdown() > threshold
+            && self.current.median().saturating_sub(self.baseline.median()) >= MIN_SLOWDOWN
+    }
+
+    pub fn answer_changed(&self) -> bool {
+        self.baseline.answer != self.current.answer
+    }
+}
+
+/// Compare each part in the current run with the same part in the baseline. Parts only in one of
+/// the runs are left out.
+pub fn compare<'a>(baseline: &Run<'a>, current: &Run<'a>) -> Vec<Comparison<'a>> {
+    current
+        .entries
+        .iter()
+        .filter_map(|&current| {
+            baseline
+                .entries
+                .iter()
+                .find(|baseline| {
+                    (baseline.year, baseline.day, baseline.part)
+                        == (current.year, current.day, current.part)
+                })
+                .map(|&baseline| Comparison { baseline, current })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(run: u64, day: usize, median_us: u64, answer: &str) -> Entry {
+        Entry {
+            run,
+            timestamp: 0,
+            commit: Some(format!("commit{}", run)),
+            dirty: false,
+            year: 2024,
+            day,
+            part: 1,
+            median_us,
+            samples: 1,
+            answer: Some(answer.to_string()),
+        }
+    }
+
+    #[test]
+    fn compares_runs() {
+        let entries = vec![
+            entry(1, 1, 1000, "11"),
+            entry(1, 2, 1000, "2"),
+            entry(1, 3, 1000, "3"),
+            entry(1, 5, 2, "5"),
+            entry(2, 1, 1050, "11"),
+            entry(2, 2, 1500, "2"),
+            entry(2, 3, 1000, "4"),
+            entry(2, 4, 1000, "5"),
+            entry(2, 5, 4, "5"),
+        ];
+
+        let runs = runs(&entries);
+        let baseline = find_run(&runs, "commit1").unwrap();
+        let current = find_run(&runs, "2").unwrap();
+        let comparisons = compare(baseline, current);
+
+        assert_eq!(comparisons.len(), 4);
+        assert!(!comparisons[0].is_slower(DEFAULT_SLOWDOWN_THRESHOLD));
+        assert!(comparisons[1].is_slower(DEFAULT_SLOWDOWN_THRESHOLD));
+        // Twice as slow, but only by a couple of microseconds.
+        assert!(!comparisons[3].is_slower(DEFAULT_SLOWDOWN_THRESHOLD));
+        assert!(!comparisons[1].answer_changed());
+        assert!(comparisons[2].answer_changed());
+    }
+}
@@ -4,73 +4,241 @@ use core::str;
 use std::{
     error::Error,
     fmt::Display,
-    fs::{create_dir, File},
-    io::{self, Read, Write},
+    fs::{self, create_dir, create_dir_all, read_dir, rename, File},
+    io::{self, Read},
     path::{Path, PathBuf},
+    time::Duration,
 };
 
+use reqwest::StatusCode;
+
+use crate::client::{AocClient, Throttle, TooManyRequests};
+
 const INPUTS_DIR_PATH: &str = "inputs";
 const AOC_COOKIE_PATH: &str = "cookie.txt";
 
-pub(crate) fn load_cookie() -> Result<String, io::Error> {
+pub fn load_cookie() -> Result<String, io::Error> {
     File::open(AOC_COOKIE_PATH).map(|mut file| {
         let mut string = String::new();
         file.read_to_string(&mut string).map(|_| string)
     })?
 }
 
-pub(crate) fn init_inputs_cache() -> Result<PathBuf, io::Error> {
+/// Get the path of the inputs cache for the given year, creating it if need be.
+pub fn init_inputs_cache(year: u16) -> Result<PathBuf, io::Error> {
     let inputs_cache_path = PathBuf::from(INPUTS_DIR_PATH);
 
     if !inputs_cache_path.exists() {
         create_dir(&inputs_cache_path)?;
     }
 
-    Ok(inputs_cache_path)
+    init_year_dir(&inputs_cache_path, year)
+}
+
+/// The year whose files were kept directly in the inputs and answers directories, before those were
+/// split up by year.
+const FLAT_LAYOUT_YEAR: u16 = 2024;
+
+/// Create the directory for a year's files within `dir`, first moving any `day_N.txt` files left
+/// directly in `dir` from before it was split up by year into the directory for 2024.
+pub fn init_year_dir(dir: &Path, year: u16) -> Result<PathBuf, io::Error> {
+    let flat_files = read_dir(dir)?
+        .filter_map(Result::ok)
+        .filter(|entry| entry.file_type().is_ok_and(|file_type| file_type.is_file()))
+        .filter(|entry| {
+            entry.file_name().to_str().is_some_and(|name| {
+                name.strip_prefix("day_")
+                    .and_then(|name| name.strip_suffix(".txt"))
+                    .is_some_and(|day| day.parse::<usize>().is_ok())
+            })
+        })
+        .collect::<Vec<_>>();
+
+    if !flat_files.is_empty() {
+        let flat_year_dir = dir.join(FLAT_LAYOUT_YEAR.to_string());
+        create_dir_all(&flat_year_dir)?;
+
+        for entry in flat_files {
+            let new_path = flat_year_dir.join(entry.file_name());
+
+            if new_path.exists() {
+                eprintln!(
+                    "Not moving {} to {}, as it already exists!",
+                    entry.path().display(),
+                    new_path.display()
+                );
+                continue;
+            }
+
+            eprintln!(
+                "Moving {} to {}",
+                entry.path().display(),
+                new_path.display()
+            );
+            rename(entry.path(), new_path)?;
+        }
+    }
+
+    let year_dir = dir.join(year.to_string());
+    create_dir_all(&year_dir)?;
+
+    Ok(year_dir)
+}
+
+/// Where the input data for a day was retrieved from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum InputSource {
+    Cached,
+    Downloaded,
+    /// A file given to us on the command line.
+    File,
+    Stdin,
+    /// One of the examples from the puzzle description.
+    Example,
+}
+
+/// Where the input for a day is kept in the inputs cache.
+pub fn cached_input_path(inputs_cache_path: &Path, day: usize) -> PathBuf {
+    inputs_cache_path.join(format!("day_{}.txt", day))
 }
 
 /// Retrieve either the cached input data for a day, or download the input from AOC and cache it.
-pub(crate) fn retrieve_input(
+/// The cache path is that for the given year. With `refresh`, the input is downloaded again even if
+/// it's cached, and replaces what was cached once it has been.
+pub fn retrieve_input(
+    client: &AocClient,
+    year: u16,
     day: usize,
-    cookie_opt: Option<&str>,
     inputs_cache_path: &Path,
-) -> Result<String, RetrieveInputError> {
-    let input_path = inputs_cache_path.join(format!("day_{}.txt", day));
+    refresh: bool,
+) -> Result<(String, InputSource), RetrieveInputError> {
+    let input_path = cached_input_path(inputs_cache_path, day);
 
-    if let Ok(mut file) = File::open(&input_path) {
-        let mut input = String::new();
-        file.read_to_string(&mut input)?;
+    if !refresh {
+        if let Ok(mut file) = File::open(&input_path) {
+            let mut input = String::new();
+            file.read_to_string(&mut input)?;
 
-        return Ok(input);
+            // Only older versions would have cached an error, but it's no more use than it was
+            // when it was downloaded.
+            if let Some(error) = error_page(&input) {
+                client.report(&format!(
+                    "The cached input for day {} is an error from AOC rather than an input, run \
+                     with --refresh to download it again",
+                    day
+                ));
+
+                return Err(error);
+            }
+
+            return Ok((input, InputSource::Cached));
+        }
     }
 
-    let cookie = cookie_opt.ok_or_else(|| RetrieveInputError::NoCookieForDownload)?;
+    if client.cookie().is_none() {
+        return Err(RetrieveInputError::NoCookieForDownload);
+    }
 
-    let input = download_input(day, cookie)?;
-    let mut file = File::create_new(&input_path)?;
-    file.write_all(input.as_bytes())?;
+    let input = download_input(client, year, day)?;
+    fs::write(&input_path, &input)?;
 
-    Ok(input)
+    Ok((input, InputSource::Downloaded))
+}
+
+/// Read input data we've been given directly, rather than the input for a day from AOC. A path of
+/// `-` means standard input.
+pub fn read_input_from(path: &Path) -> Result<(String, InputSource), io::Error> {
+    read_input_or_stdin(path, io::stdin())
+}
+
+fn read_input_or_stdin(
+    path: &Path,
+    mut stdin: impl Read,
+) -> Result<(String, InputSource), io::Error> {
+    let mut input = String::new();
+
+    if path == Path::new("-") {
+        stdin.read_to_string(&mut input)?;
+
+        return Ok((input, InputSource::Stdin));
+    }
+
+    File::open(path)?.read_to_string(&mut input)?;
+
+    Ok((input, InputSource::File))
 }
 
 /// Download the solution input for the given day.
-fn download_input(day: usize, cookie: &str) -> Result<String, reqwest::Error> {
-    println!("Downloading input for day {}...", day);
+fn download_input(client: &AocClient, year: u16, day: usize) -> Result<String, RetrieveInputError> {
+    client.report(&format!("Downloading input for {} day {}...", year, day));
+
+    let response = client.get(&format!("/{}/day/{}/input", year, day)).send()?;
+
+    client.check_rate_limit(&response)?;
+
+    let status = response.status();
 
-    let client = reqwest::blocking::Client::new();
+    check_response(status, response.text()?)
+}
+
+/// What AOC says instead of giving an input, when the cookie has expired or isn't for a login.
+const NOT_LOGGED_IN_MESSAGE: &str = "Please log in to get your puzzle input";
+/// What AOC says instead of giving an input, when asked for a day that hasn't unlocked yet.
+const NOT_UNLOCKED_MESSAGE: &str = "before it unlocks";
+
+/// The error AOC gave in place of an input, if that's what `body` is.
+fn error_page(body: &str) -> Option<RetrieveInputError> {
+    if body.contains(NOT_LOGGED_IN_MESSAGE) {
+        Some(RetrieveInputError::NotLoggedIn)
+    } else if body.contains(NOT_UNLOCKED_MESSAGE) {
+        Some(RetrieveInputError::NotUnlocked)
+    } else {
+        None
+    }
+}
+
+/// Make sure a response from AOC is actually an input, rather than an error, so that errors never
+/// end up cached as if they were inputs.
+fn check_response(status: StatusCode, body: String) -> Result<String, RetrieveInputError> {
+    if let Some(error) = error_page(&body) {
+        return Err(error);
+    }
+
+    if !status.is_success() {
+        return Err(RetrieveInputError::Status(status));
+    }
 
-    client
-        .get(format!("https://adventofcode.com/2024/day/{}/input", day))
-        .header(reqwest::header::COOKIE, cookie)
-        .send()?
-        .text()
+    Ok(body)
 }
 
 #[derive(Debug)]
-pub(crate) enum RetrieveInputError {
+pub enum RetrieveInputError {
     Io(io::Error),
     Network(reqwest::Error),
     NoCookieForDownload,
+    /// AOC didn't accept the cookie, most likely as it has expired.
+    NotLoggedIn,
+    /// The day's puzzle hasn't unlocked yet.
+    NotUnlocked,
+    /// AOC responded with an error status not covered by the others.
+    Status(StatusCode),
+    TooManyRequests(TooManyRequests),
+}
+
+impl RetrieveInputError {
+    /// A short, stable name for the kind of error, for tools consuming our output.
+    pub fn kind(&self) -> &'static str {
+        match self {
+            RetrieveInputError::Io(_) => "io",
+            RetrieveInputError::Network(_) => "network",
+            RetrieveInputError::NoCookieForDownload => "no_cookie",
+            RetrieveInputError::NotLoggedIn => "not_logged_in",
+            RetrieveInputError::NotUnlocked => "not_unlocked",
+            RetrieveInputError::Status(_) => "status",
+            RetrieveInputError::TooManyRequests(_) => "too_many_requests",
+        }
+    }
 }
 
 impl Display for RetrieveInputError {
@@ -81,6 +249,15 @@ impl Display for RetrieveInputError {
                 write!(f, "No cookie supplied to download from AOC")
             }
             RetrieveInputError::Network(error) => error.fmt(f),
+            RetrieveInputError::NotLoggedIn => write!(
+                f,
+                "AOC didn't accept the cookie to download with, it may have expired"
+            ),
+            RetrieveInputError::NotUnlocked => write!(f, "The puzzle hasn't unlocked yet"),
+            RetrieveInputError::Status(status) => {
+                write!(f, "AOC responded with an error: {}", status)
+            }
+            RetrieveInputError::TooManyRequests(error) => error.fmt(f),
         }
     }
 }
@@ -90,7 +267,11 @@ impl Error for RetrieveInputError {
         match self {
             RetrieveInputError::Io(error) => Some(error),
             RetrieveInputError::Network(error) => Some(error),
-            RetrieveInputError::NoCookieForDownload => None,
+            RetrieveInputError::TooManyRequests(error) => Some(error),
+            RetrieveInputError::NoCookieForDownload
+            | RetrieveInputError::NotLoggedIn
+            | RetrieveInputError::NotUnlocked
+            | RetrieveInputError::Status(_) => None,
         }
     }
 }
@@ -106,3 +287,103 @@ impl From<reqwest::Error> for RetrieveInputError {
         RetrieveInputError::Network(value)
     }
 }
+
+impl From<TooManyRequests> for RetrieveInputError {
+    fn from(value: TooManyRequests) -> Self {
+        RetrieveInputError::TooManyRequests(value)
+    }
+}
+
+/// A throttle kept at the top of the inputs cache, shared by every year's inputs and everything
+/// else run from here.
+pub fn inputs_cache_throttle(interval: Duration) -> Throttle {
+    Throttle::new(Path::new(INPUTS_DIR_PATH), interval)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::support::temp_dir::TempDir;
+
+    #[test]
+    fn rejects_error_responses() {
+        let check = |status, body: &str| check_response(status, body.to_string());
+
+        assert_eq!(check(StatusCode::OK, "3   4\n").unwrap(), "3   4\n");
+        assert!(matches!(
+            check(
+                StatusCode::BAD_REQUEST,
+                "Puzzle inputs differ by user.  Please log in to get your puzzle input.\n"
+            ),
+            Err(RetrieveInputError::NotLoggedIn)
+        ));
+        assert!(matches!(
+            check(
+                StatusCode::NOT_FOUND,
+                "Please don't repeatedly request this endpoint before it unlocks! The calendar \
+                 countdown is synchronized with the server time; the link will be enabled on the \
+                 calendar the instant this puzzle becomes available.\n"
+            ),
+            Err(RetrieveInputError::NotUnlocked)
+        ));
+        assert!(matches!(
+            check(StatusCode::INTERNAL_SERVER_ERROR, "Oops"),
+            Err(RetrieveInputError::Status(
+                StatusCode::INTERNAL_SERVER_ERROR
+            ))
+        ));
+    }
+
+    #[test]
+    fn reads_input_from_files_or_stdin() {
+        let dir = TempDir::new("input-file");
+        let path = dir.path().join("input.txt");
+        fs::write(&path, "3   4\n").unwrap();
+
+        let read = |path: &Path| read_input_or_stdin(path, "4   3\n".as_bytes());
+
+        assert_eq!(
+            read(&path).unwrap(),
+            ("3   4\n".to_string(), InputSource::File)
+        );
+        assert_eq!(
+            read(Path::new("-")).unwrap(),
+            ("4   3\n".to_string(), InputSource::Stdin)
+        );
+        assert!(read(&dir.path().join("missing.txt")).is_err());
+    }
+
+    #[test]
+    fn moves_flat_layout_into_year_dir() {
+        let inputs = TempDir::new("flat-layout");
+        let year_dir = inputs.path().join("2024");
+
+        fs::write(inputs.path().join("day_1.txt"), "1\n").unwrap();
+        fs::write(inputs.path().join("day_2.txt"), "flat\n").unwrap();
+        fs::write(inputs.path().join("notes.txt"), "").unwrap();
+        fs::create_dir(&year_dir).unwrap();
+        fs::write(year_dir.join("day_2.txt"), "split\n").unwrap();
+
+        assert_eq!(init_year_dir(inputs.path(), 2024).unwrap(), year_dir);
+        assert_eq!(
+            fs::read_to_string(year_dir.join("day_1.txt")).unwrap(),
+            "1\n"
+        );
+        assert!(!inputs.path().join("day_1.txt").exists());
+
+        // Nothing already split up by year is overwritten, and nothing but days' files is moved.
+        assert_eq!(
+            fs::read_to_string(year_dir.join("day_2.txt")).unwrap(),
+            "split\n"
+        );
+        assert!(inputs.path().join("day_2.txt").exists());
+        assert!(inputs.path().join("notes.txt").exists());
+
+        // Other years are only ever split up.
+        assert_eq!(
+            init_year_dir(inputs.path(), 2023).unwrap(),
+            inputs.path().join("2023")
+        );
+        assert!(inputs.path().join("day_2.txt").exists());
+    }
+}
@@ -0,0 +1,14 @@
+//! Solutions to Advent of Code, and the inputs cache they're run on.
+//!
+//! This is what other tools can build on: the registry of each year's days in [days], the inputs
+//! cache in [input], along with the [client] it downloads with, and the grid and iterator helpers in
+//! [utils]. Running, timing and reporting on days is left to the command line runner.
+
+pub mod client;
+pub mod days;
+pub mod input;
+pub mod utils;
+
+#[cfg(test)]
+#[path = "../tests/support/mod.rs"]
+mod support;
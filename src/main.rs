@@ -1,49 +1,1264 @@
-use std::{error::Error, time::Instant};
+use std::{
+    env,
+    ffi::OsString,
+    fs,
+    path::{Path, PathBuf},
+    process::ExitCode,
+    time::{Duration, Instant},
+};
 
-use clap::Parser;
+use advent_of_code_2024::{
+    client::{self, AocClient, ClientConfig},
+    days::{self, Day, Example, Part, Year},
+    input::{self, InputSource},
+    utils,
+};
+use clap::{
+    builder::PossibleValue, error::ErrorKind, parser::ValueSource, Args, CommandFactory,
+    FromArgMatches, Subcommand, ValueEnum,
+};
+use itertools::Itertools;
+use rayon::iter::{IntoParallelRefIterator, ParallelIterator};
 
-mod days;
-mod input;
-mod utils;
+use crate::{
+    answers::ExpectedAnswers,
+    bench::Total,
+    history::Measurement,
+    output::{DayError, DayReport, OutputFormat, ParallelTotal, Printer, Summary},
+    runner::{BenchOptions, DayRun, Failure, PartRun, Timing},
+    selection::{DayRange, Selection},
+    submissions::Submission,
+    submit::SubmitOutcome,
+};
+
+// Only the solutions and the inputs cache are in the library. Everything else is only for running
+// them, so lives here.
+mod answers;
+mod bench;
+mod history;
+mod mem;
+mod output;
+mod panics;
+mod puzzle;
+mod report;
+mod runner;
+mod scaffold;
+mod selection;
+mod submissions;
+mod submit;
+#[cfg(test)]
+#[path = "../tests/support/mod.rs"]
+mod support;
+mod tui;
+
+#[global_allocator]
+static ALLOCATOR: mem::CountingAllocator = mem::CountingAllocator;
 
 #[derive(clap::Parser)]
 #[command(about)]
 struct Cli {
+    #[command(subcommand)]
+    command: Option<Command>,
+
+    /// The year whose solutions to run. Defaults to the latest year we have solutions for.
+    #[arg(short, long, global = true)]
+    year: Option<u16>,
+
+    /// Download the inputs of the days being run again, replacing those cached, such as when a
+    /// cached input looks like an error from AOC. For `read` and `examples`, the puzzle is
+    /// downloaded again instead.
+    #[arg(long, global = true)]
+    refresh: bool,
+
+    #[command(flatten)]
+    run_args: RunArgs,
+
+    /// Where Advent of Code lives, for testing against a stand-in for it.
+    #[arg(long, value_name = "URL", global = true, default_value = client::DEFAULT_BASE_URL)]
+    base_url: String,
+
+    /// How long to wait for Advent of Code to respond, e.g. `30s`.
+    #[arg(
+        long,
+        value_name = "DURATION",
+        global = true,
+        value_parser = humantime::parse_duration,
+        default_value = "30s"
+    )]
+    http_timeout: Duration,
+
+    /// How to identify ourselves to Advent of Code. It asks for this to include a way of
+    /// contacting whoever is running the tool, such as an email address.
+    #[arg(long, value_name = "AGENT", global = true, default_value = client::DEFAULT_USER_AGENT)]
+    user_agent: String,
+}
+
+/// Options for running days, for when no subcommand is given.
+#[derive(Args)]
+struct RunArgs {
+    /// The days to run, as a list of days and ranges of days, e.g. `1-10,12`. Defaults to every
+    /// day.
     #[arg(short, long, value_name = "DAYS", value_delimiter = ',')]
-    run: Option<Vec<usize>>,
+    run: Option<Vec<DayRange>>,
+
+    /// Days not to run, as a list of days and ranges of days.
+    #[arg(long, value_name = "DAYS", value_delimiter = ',')]
+    skip: Vec<DayRange>,
+
+    /// Only run the latest day there's a solution for, out of those otherwise selected.
+    #[arg(long)]
+    latest: bool,
+
+    /// Only run days with parts that haven't been solved yet, going by whether they still give the
+    /// not-yet-implemented placeholder on their examples.
+    #[arg(long)]
+    unsolved: bool,
+
+    /// Only run the given part of each day, rather than both.
+    #[arg(short, long, value_enum)]
+    part: Option<PartArg>,
+
+    /// Run the selected day on the input in this file, rather than the day's own input. Use `-` to
+    /// read the input from stdin. The answers aren't checked, since they won't be the day's own.
+    #[arg(short, long, value_name = "PATH")]
+    input: Option<PathBuf>,
+
+    /// Record the answers from this run as the expected answers for each day that was run.
+    #[arg(long, conflicts_with = "input")]
+    save_answers: bool,
+
+    /// Run each day on the examples from its puzzle description rather than its real input, and
+    /// check them against the answers the puzzle gives.
+    #[arg(long, conflicts_with_all = ["input", "save_answers"])]
+    examples: bool,
+
+    /// Run each day repeatedly and report statistics on its timings, rather than timing one run.
+    #[arg(long)]
+    bench: bool,
+
+    /// Number of untimed runs of each day to do before measuring, when benchmarking.
+    #[arg(long, value_name = "RUNS", default_value_t = bench::DEFAULT_WARMUP_RUNS, requires = "bench")]
+    warmup: usize,
+
+    /// Number of timed runs of each day, when benchmarking.
+    #[arg(long, value_name = "RUNS", default_value_t = bench::DEFAULT_TIMED_RUNS, requires = "bench")]
+    runs: usize,
+
+    /// Run the selected days at the same time, rather than one after another. Results are still
+    /// printed in order of day, once every day has finished. Each day is run on a thread pool of
+    /// its own, so that the CPU time it takes can be shown alongside its wall-clock time. The times
+    /// aren't recorded in the history, as days slow each other down.
+    #[arg(long, conflicts_with = "bench")]
+    parallel: bool,
+
+    /// Give up on any day that takes longer than this to solve, e.g. `10s` or `500ms`. Days are
+    /// then run on their own thread, which carries on in the background if it times out.
+    #[arg(long, value_name = "DURATION", value_parser = humantime::parse_duration)]
+    timeout: Option<Duration>,
+
+    /// Count the allocations made by each day's parse and parts, and the most memory each had
+    /// allocated at once. The times aren't recorded in the history, as counting slows days down.
+    #[arg(long, conflicts_with_all = ["bench", "parallel"])]
+    mem: bool,
+
+    /// The format to write the results of each day in.
+    #[arg(short, long, value_enum, default_value_t)]
+    output: OutputFormat,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Compare a run from the history with an earlier one, highlighting any parts that got slower
+    /// or whose answers changed.
+    Compare {
+        /// The run to compare against, either by its number or by a commit it was made at. Defaults
+        /// to the run before the one being compared.
+        baseline: Option<String>,
+
+        /// The run to compare, either by its number or by a commit it was made at. Defaults to the
+        /// latest run.
+        #[arg(long, value_name = "RUN")]
+        current: Option<String>,
+
+        /// How much slower a part has to get to count, as a percentage of its baseline time. It
+        /// also has to get at least 100µs slower, as parts any faster vary more than that.
+        #[arg(long, value_name = "PERCENT", default_value_t = history::DEFAULT_SLOWDOWN_THRESHOLD)]
+        threshold: f64,
+    },
+
+    /// Run every day and write a Markdown table of the results into a file, such as the README.
+    /// The table replaces whatever is between the `<!-- results start -->` and
+    /// `<!-- results end -->` lines of the file.
+    Report {
+        /// The file to write the table into.
+        path: PathBuf,
+    },
+
+    /// Start on a new day, creating its module from a template, and downloading its input if
+    /// there's a cookie to do so with.
+    New {
+        /// The day to start on.
+        day: usize,
+
+        /// The title of the day's puzzle.
+        #[arg(short, long)]
+        title: Option<String>,
+    },
+
+    /// Show the description of a day's puzzle. It's downloaded once and cached next to the inputs,
+    /// so it can be read again without a connection, and downloaded again for part 2 once that's
+    /// been unlocked.
+    Read {
+        /// The day to read the puzzle for.
+        day: usize,
+    },
+
+    /// List the code blocks in a day's puzzle description, which are where its examples are given,
+    /// and add one to the day's examples.
+    Examples {
+        /// The day to find examples for.
+        day: usize,
+
+        /// Add the code block with this number to the day's examples, with no answers given yet.
+        #[arg(long, value_name = "NUMBER")]
+        save: Option<usize>,
+    },
+
+    /// Browse the days in an interactive dashboard, running them and looking through their results.
+    Tui,
+
+    /// Run a part of a day on its input, and submit the answer to Advent of Code.
+    Submit {
+        /// The day to submit an answer for.
+        day: usize,
+
+        /// The part to submit an answer for.
+        #[arg(value_enum)]
+        part: PartArg,
+    },
+}
+
+/// A part of a day, given on the command line by its number.
+#[derive(Debug, Clone, Copy)]
+struct PartArg(Part);
+
+impl ValueEnum for PartArg {
+    fn value_variants<'a>() -> &'a [Self] {
+        &[PartArg(Part::One), PartArg(Part::Two)]
+    }
+
+    fn to_possible_value(&self) -> Option<PossibleValue> {
+        Some(PossibleValue::new(match self.0 {
+            Part::One => "1",
+            Part::Two => "2",
+        }))
+    }
 }
 
-fn main() {
-    let cli = Cli::parse();
+impl Cli {
+    /// Parse the command line, along with the conflicts clap can't check for itself: global
+    /// arguments can't conflict with others, and the options for running days would stop global
+    /// ones coming before a subcommand if they conflicted with subcommands.
+    fn try_parse_checked_from<T: Into<OsString> + Clone>(
+        args: impl IntoIterator<Item = T>,
+    ) -> Result<Self, clap::Error> {
+        let matches = Cli::command().try_get_matches_from(args)?;
+        let cli = Cli::from_arg_matches(&matches)?;
+
+        if cli.refresh && cli.run_args.input.is_some() {
+            return Err(Cli::command().error(
+                ErrorKind::ArgumentConflict,
+                "--refresh can't be used with --input, as the input isn't downloaded",
+            ));
+        }
+
+        if let Some(subcommand) = matches.subcommand_name() {
+            let run_args = RunArgs::augment_args(clap::Command::new(""));
+            let given = run_args.get_arguments().find(|arg| {
+                matches.value_source(arg.get_id().as_str()) == Some(ValueSource::CommandLine)
+            });
+
+            if let Some(arg) = given {
+                return Err(Cli::command().error(
+                    ErrorKind::ArgumentConflict,
+                    format!(
+                        "--{} can't be used with the {} subcommand",
+                        arg.get_long().unwrap_or_default(),
+                        subcommand
+                    ),
+                ));
+            }
+        }
+
+        Ok(cli)
+    }
+}
+
+fn main() -> ExitCode {
+    let cli = Cli::try_parse_checked_from(env::args_os()).unwrap_or_else(|err| err.exit());
+    let run_args = &cli.run_args;
+
+    panics::install_hook();
+
+    if run_args.mem {
+        mem::enable();
+    }
+
+    if let Some(Command::Compare {
+        baseline,
+        current,
+        threshold,
+    }) = &cli.command
+    {
+        return compare_runs(baseline.as_deref(), current.as_deref(), *threshold);
+    }
+
+    let year = cli.year.unwrap_or_else(days::latest_year);
+
+    // Starting on a year, or reading its puzzles, doesn't need any solutions for it yet.
+    let unsolved_year = Year { year, days: &[] };
+
+    let registry = match days::find_year(year) {
+        Some(registry) => registry,
+        None if matches!(
+            cli.command,
+            Some(Command::New { .. } | Command::Read { .. })
+        ) =>
+        {
+            &unsolved_year
+        }
+        None => {
+            let years = days::YEARS
+                .iter()
+                .map(|registry| registry.year.to_string())
+                .join(", ");
+
+            Cli::command()
+                .error(
+                    ErrorKind::InvalidValue,
+                    format!(
+                        "There are no solutions for {}, only for {}, start on it with `new`",
+                        year, years
+                    ),
+                )
+                .exit();
+        }
+    };
 
     let inputs_cache_path =
-        input::init_inputs_cache().expect("Failed to initialize inputs cache path!");
-    let cookie_opt = input::load_cookie().ok();
+        input::init_inputs_cache(year).expect("Failed to initialize inputs cache path!");
+    let answers_store_path =
+        answers::init_answers_store(year).expect("Failed to initialize answers store path!");
 
-    days::DAYS
-        .iter()
-        .enumerate()
-        .map(|(i, day_func)| (i + 1, day_func))
-        .filter(|(day, _)| match &cli.run {
-            Some(days) => days.contains(day),
-            None => true,
+    let client_config = ClientConfig {
+        base_url: cli.base_url.clone(),
+        timeout: cli.http_timeout,
+        user_agent: cli.user_agent.clone(),
+    };
+
+    let throttle = input::inputs_cache_throttle(client::DEFAULT_REQUEST_INTERVAL);
+
+    let client = match AocClient::new(&client_config, input::load_cookie().ok()) {
+        Ok(client) => client.with_throttle(throttle),
+        Err(err) => {
+            eprintln!("Failed to set up the connection to AOC: {}", err);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    if let Some(Command::New { day, title }) = &cli.command {
+        if !(1..=days::LAST_DAY).contains(day) {
+            Cli::command()
+                .error(
+                    ErrorKind::InvalidValue,
+                    format!("There is no day {}, only days 1 to {}", day, days::LAST_DAY),
+                )
+                .exit();
+        }
+
+        if registry.find_day(*day).is_some() {
+            eprintln!("Day {} of {} already has a solution!", day, year);
+            return ExitCode::FAILURE;
+        }
+
+        return new_day(year, *day, title.as_deref(), &client, &inputs_cache_path);
+    }
+
+    if let Some(Command::Read { day }) = &cli.command {
+        if !(1..=days::LAST_DAY).contains(day) {
+            Cli::command()
+                .error(
+                    ErrorKind::InvalidValue,
+                    format!("There is no day {}, only days 1 to {}", day, days::LAST_DAY),
+                )
+                .exit();
+        }
+
+        return read_puzzle(
+            &client,
+            year,
+            *day,
+            &inputs_cache_path,
+            &answers_store_path,
+            cli.refresh,
+        );
+    }
+
+    if let Some(Command::Examples { day, save }) = &cli.command {
+        let Some(day_solution) = registry.find_day(*day) else {
+            Cli::command()
+                .error(
+                    ErrorKind::InvalidValue,
+                    format!(
+                        "There is no solution for day {} of {}, start on it with `new {}`",
+                        day, year, day
+                    ),
+                )
+                .exit();
+        };
+
+        return puzzle_examples(
+            &client,
+            year,
+            day_solution,
+            *save,
+            &inputs_cache_path,
+            &answers_store_path,
+            cli.refresh,
+        );
+    }
+
+    let parts = match run_args.part {
+        Some(PartArg(part)) => vec![part],
+        None => Part::ALL.to_vec(),
+    };
+
+    let bench_options = run_args.bench.then_some(BenchOptions {
+        warmup_runs: run_args.warmup,
+        timed_runs: run_args.runs,
+    });
+
+    let selection = Selection {
+        run: run_args.run.clone(),
+        skip: run_args.skip.clone(),
+        latest: run_args.latest,
+        unsolved: run_args.unsolved,
+    };
+
+    let selected_days = match selection.select(registry) {
+        Ok(selected_days) => selected_days,
+        Err(err) => Cli::command()
+            .error(ErrorKind::InvalidValue, err.to_string())
+            .exit(),
+    };
+
+    // Checked before reading the input, which could mean waiting on all of stdin.
+    if run_args.input.is_some() && selected_days.len() != 1 {
+        Cli::command()
+            .error(
+                ErrorKind::ArgumentConflict,
+                "--input can only be used when running a single day",
+            )
+            .exit();
+    }
+
+    let input_override = match &run_args.input {
+        Some(path) => match input::read_input_from(path) {
+            Ok(input) => Some(input),
+            Err(err) => {
+                eprintln!("Failed to read input from {}: {}", path.display(), err);
+                return ExitCode::FAILURE;
+            }
+        },
+        None => None,
+    };
+
+    let context = RunContext {
+        year,
+        parts: &parts,
+        bench_options,
+        timeout: run_args.timeout,
+        input_override: input_override.as_ref(),
+        client: &client,
+        inputs_cache_path: &inputs_cache_path,
+        answers_store_path: &answers_store_path,
+        refresh: cli.refresh,
+    };
+
+    if let Some(Command::Report { path }) = &cli.command {
+        return write_report(registry.days, &context, path);
+    }
+
+    if let Some(Command::Submit { day, part }) = &cli.command {
+        let Some(day_solution) = registry.find_day(*day) else {
+            Cli::command()
+                .error(
+                    ErrorKind::InvalidValue,
+                    format!("There is no solution for day {} of {}", day, year),
+                )
+                .exit();
+        };
+
+        return submit_answer(day_solution, part.0, &context);
+    }
+
+    if let Some(Command::Tui) = &cli.command {
+        if let Err(err) = tui::run(registry.days, &context) {
+            eprintln!("The dashboard failed: {}", err);
+            return ExitCode::FAILURE;
+        }
+
+        return ExitCode::SUCCESS;
+    }
+
+    let tasks = selected_days
+        .into_iter()
+        .flat_map(|day_solution| {
+            if !run_args.examples {
+                return vec![Task {
+                    day_solution,
+                    example: None,
+                }];
+            }
+
+            day_solution
+                .examples
+                .iter()
+                .enumerate()
+                .filter(|(_, example)| parts.iter().any(|&part| example.answer(part).is_some()))
+                .map(|(i, example)| Task {
+                    day_solution,
+                    example: Some((i + 1, example)),
+                })
+                .collect()
         })
-        .map::<Result<_, Box<dyn Error>>, _>(|(day, day_func)| {
+        .collect::<Vec<_>>();
+
+    let mut printer = Printer::new(run_args.output);
+
+    if run_args.parallel {
+        runner::enable_cpu_time();
+        printer = printer.with_cpu_time();
+    }
+    let mut summary = Summary::default();
+    let mut bench_stats = Vec::new();
+    let mut measurements = Vec::new();
+
+    let mut handle_report = |printer: &mut Printer, solved: SolvedDay| {
+        let SolvedDay { report, expected } = solved;
+
+        match &report.outcome {
+            Ok(run) => {
+                if run.is_regression() {
+                    summary.any_regressed = true;
+                }
+
+                if run.any_panicked() {
+                    summary.any_panicked = true;
+                }
+
+                if let Timing::Bench(stats) = run.total {
+                    bench_stats.push(stats);
+                }
+
+                // Only times comparable with the rest of the history are worth recording.
+                if report.example.is_none()
+                    && run_args.input.is_none()
+                    && !run_args.mem
+                    && !run_args.parallel
+                {
+                    measurements.extend(Measurement::of_day(report.day, run));
+                }
+
+                if run_args.save_answers {
+                    save_answers(report.day, run, expected, &answers_store_path);
+                }
+            }
+            Err(DayError::Parse(failure)) => {
+                // A day we know the answers to that no longer produces any is just as broken as
+                // one producing the wrong answers.
+                if expected.is_some_and(|expected| {
+                    parts.iter().any(|&part| expected.for_part(part).is_some())
+                }) {
+                    summary.any_regressed = true;
+                }
+
+                if let Failure::Panic(_) = failure {
+                    summary.any_panicked = true;
+                }
+            }
+            Err(DayError::Timeout(_)) => summary.any_timed_out = true,
+            Err(DayError::Input(_)) => {}
+        }
+
+        if let Err(err) = printer.print_day(&report) {
+            eprintln!("Failed to write the results of day {}: {}", report.day, err);
+        }
+    };
+
+    let now = Instant::now();
+    let cpu_start = runner::process_cpu_time();
+
+    if run_args.parallel {
+        // Everything is run before anything is printed, so that the results still come out in
+        // order of day, however long each one takes.
+        let solved_days = tasks
+            .par_iter()
+            .map(|task| solve_day(task, &context))
+            .collect::<Vec<_>>();
+
+        for (task, solved) in tasks.iter().zip(solved_days) {
+            printer.begin_day(task.day_solution, solved.report.example);
+            handle_report(&mut printer, solved);
+        }
+    } else {
+        for task in &tasks {
+            printer.begin_day(task.day_solution, task.example.map(|(number, _)| number));
+            handle_report(&mut printer, solve_day(task, &context));
+        }
+    }
+
+    let wall_clock = now.elapsed();
+
+    summary.bench_total = run_args.bench.then(|| bench_stats.iter().sum::<Total>());
+    summary.parallel_total = run_args.parallel.then(|| ParallelTotal {
+        wall_clock,
+        cpu: cpu_start
+            .zip(runner::process_cpu_time())
+            .map(|(start, end)| end - start),
+    });
+
+    printer.finish(&summary);
+
+    if !measurements.is_empty() {
+        let history_path = Path::new(history::HISTORY_FILE_PATH);
+
+        if let Err(err) = history::record_run(history_path, year, &measurements) {
+            eprintln!(
+                "Failed to record this run in {}: {}",
+                history_path.display(),
+                err
+            );
+        }
+    }
+
+    if summary.is_failure() {
+        return ExitCode::FAILURE;
+    }
+
+    ExitCode::SUCCESS
+}
+
+/// Compare two runs from the history, printing how each part they have in common changed.
+fn compare_runs(baseline: Option<&str>, current: Option<&str>, threshold: f64) -> ExitCode {
+    let history_path = Path::new(history::HISTORY_FILE_PATH);
+
+    let entries = match history::load(history_path) {
+        Ok(entries) => entries,
+        Err(err) => {
+            eprintln!("Failed to load {}: {}", history_path.display(), err);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let runs = history::runs(&entries);
+
+    let current_run = match current {
+        Some(reference) => history::find_run(&runs, reference),
+        None => runs.last(),
+    };
+
+    let Some(current_run) = current_run else {
+        eprintln!("Couldn't find the run to compare in the history!");
+        return ExitCode::FAILURE;
+    };
+
+    let baseline_run = match baseline {
+        Some(reference) => history::find_run(&runs, reference),
+        None => runs
+            .iter()
+            .rev()
+            .find(|run| run.number < current_run.number),
+    };
+
+    let Some(baseline_run) = baseline_run else {
+        eprintln!("Couldn't find the run to compare against in the history!");
+        return ExitCode::FAILURE;
+    };
+
+    println!(
+        "Comparing {} against {}",
+        current_run.describe(),
+        baseline_run.describe()
+    );
+    println!();
+
+    let comparisons = history::compare(baseline_run, current_run);
+
+    if comparisons.is_empty() {
+        println!("The runs have no parts in common!");
+        return ExitCode::SUCCESS;
+    }
+
+    let mut any_slower = false;
+    let mut any_changed = false;
+
+    for comparison in &comparisons {
+        let current = comparison.current;
+        let mut flags = Vec::new();
+
+        if comparison.is_slower(threshold) {
+            any_slower = true;
+            flags.push("✗ slower".to_string());
+        }
+
+        if comparison.answer_changed() {
+            any_changed = true;
+            flags.push(format!(
+                "✗ answer changed from {}",
+                comparison.baseline.answer.as_deref().unwrap_or("nothing")
+            ));
+        }
+
+        println!(
+            "{} Day {:>2} Part {} :: {}µs -> {}µs ({:+.1}%){}",
+            current.year,
+            current.day,
+            current.part,
+            comparison.baseline.median_us,
+            current.median_us,
+            comparison.slowdown(),
+            flags
+                .iter()
+                .map(|flag| format!(" {}", flag))
+                .collect::<String>()
+        );
+    }
+
+    if any_slower {
+        println!();
+        println!(
+            "Some parts got more than {}% and {}µs slower!",
+            threshold,
+            history::MIN_SLOWDOWN.as_micros()
+        );
+    }
+
+    if any_changed {
+        println!();
+        println!("Some parts' answers changed!");
+    }
+
+    if any_slower || any_changed {
+        return ExitCode::FAILURE;
+    }
+
+    ExitCode::SUCCESS
+}
+
+/// Create the module for a new day, and get its input ready.
+fn new_day(
+    year: u16,
+    day: usize,
+    title: Option<&str>,
+    client: &AocClient,
+    inputs_cache_path: &Path,
+) -> ExitCode {
+    match scaffold::create_day_module(&scaffold::days_dir(year), day, title) {
+        Ok(path) => eprintln!("Created {}", path.display()),
+        Err(err) => {
+            eprintln!("Failed to create the module for day {}: {}", day, err);
+            return ExitCode::FAILURE;
+        }
+    }
+
+    if client.cookie().is_none() {
+        eprintln!(
+            "No cookie to download the input with, so it will be downloaded on the first run"
+        );
+        return ExitCode::SUCCESS;
+    }
+
+    match input::retrieve_input(client, year, day, inputs_cache_path, false) {
+        Ok((_, InputSource::Cached)) => eprintln!("The input for day {} is already cached", day),
+        Ok(_) => eprintln!("Downloaded the input for day {}", day),
+        Err(err) => {
+            eprintln!("Failed to download the input for day {}: {}", day, err);
+            return ExitCode::FAILURE;
+        }
+    }
+
+    ExitCode::SUCCESS
+}
+
+/// Whether we've recorded an answer to part 1 of a day, either as the expected answer or as a
+/// correct submission, in which case part 2 will have been unlocked.
+fn part_1_solved(year: u16, day: usize, answers_store_path: &Path) -> bool {
+    let expected = answers::load_answers(day, answers_store_path)
+        .ok()
+        .flatten();
+
+    if expected.is_some_and(|expected| expected.for_part(Part::One).is_some()) {
+        return true;
+    }
+
+    submissions::init_submissions_log(year)
+        .and_then(|path| submissions::load_submissions(day, &path))
+        .is_ok_and(|submissions| {
+            submissions.iter().any(|submission| {
+                submission.part == Part::One.number()
+                    && submission.outcome == SubmitOutcome::Correct
+            })
+        })
+}
+
+/// Print the description of a day's puzzle, retrieving it if it isn't cached.
+fn read_puzzle(
+    client: &AocClient,
+    year: u16,
+    day: usize,
+    inputs_cache_path: &Path,
+    answers_store_path: &Path,
+    refresh: bool,
+) -> ExitCode {
+    let page = match puzzle::retrieve_puzzle(
+        client,
+        year,
+        day,
+        inputs_cache_path,
+        refresh,
+        part_1_solved(year, day, answers_store_path),
+    ) {
+        Ok((page, _)) => page,
+        Err(err) => {
+            eprintln!("Failed to retrieve the puzzle for day {}: {}", day, err);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    match puzzle::render_puzzle(&page) {
+        Some(text) => {
+            print!("{}", text);
+            ExitCode::SUCCESS
+        }
+        None => {
+            eprintln!("There's no puzzle description on the page for day {}", day);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+/// List the code blocks in a day's puzzle description, or add the one numbered `save` to the day's
+/// examples.
+fn puzzle_examples(
+    client: &AocClient,
+    year: u16,
+    day: &Day,
+    save: Option<usize>,
+    inputs_cache_path: &Path,
+    answers_store_path: &Path,
+    refresh: bool,
+) -> ExitCode {
+    let page = match puzzle::retrieve_puzzle(
+        client,
+        year,
+        day.number,
+        inputs_cache_path,
+        refresh,
+        part_1_solved(year, day.number, answers_store_path),
+    ) {
+        Ok((page, _)) => page,
+        Err(err) => {
+            eprintln!(
+                "Failed to retrieve the puzzle for day {}: {}",
+                day.number, err
+            );
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let blocks = puzzle::example_blocks(&page);
+
+    let Some(number) = save else {
+        if blocks.is_empty() {
+            eprintln!("The puzzle for day {} has no code blocks", day.number);
+        }
+
+        for (index, block) in blocks.iter().enumerate() {
+            println!("[{}]", index + 1);
+
+            for line in block.lines() {
+                println!("    {}", line);
+            }
+
             println!();
-            println!("--- Day {} ---", day);
+        }
+
+        return ExitCode::SUCCESS;
+    };
+
+    let Some(block) = number.checked_sub(1).and_then(|index| blocks.get(index)) else {
+        eprintln!(
+            "There is no code block {} in the puzzle for day {}, only {}",
+            number,
+            day.number,
+            blocks.len()
+        );
+        return ExitCode::FAILURE;
+    };
+
+    match scaffold::add_example_to_day_module(&scaffold::days_dir(year), day.number, block) {
+        Ok(path) => {
+            eprintln!(
+                "Added code block {} to the examples in {}. Fill in its answers once they're known.",
+                number,
+                path.display()
+            );
+            ExitCode::SUCCESS
+        }
+        Err(err) => {
+            eprintln!("Failed to add the example for day {}: {}", day.number, err);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+/// Run a part of a day, and submit its answer. If it's right, it's saved as the expected answer.
+fn submit_answer(day: &Day, part: Part, context: &RunContext) -> ExitCode {
+    let context = RunContext {
+        parts: &[part],
+        ..*context
+    };
+
+    let task = Task {
+        day_solution: day,
+        example: None,
+    };
+
+    let SolvedDay { report, expected } = solve_day(&task, &context);
+
+    let answer = match report.outcome.as_ref().map(|run| run.part(part)) {
+        Ok(Some(PartRun {
+            outcome: Ok(answer),
+            ..
+        })) => answer,
+        _ => {
+            eprintln!("Day {} didn't produce an answer to submit:", day.number);
+            eprint!("{}", output::day_text(&report));
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let submissions_log_path = match submissions::init_submissions_log(context.year) {
+        Ok(path) => path,
+        Err(err) => {
+            eprintln!("Failed to initialize the submission log: {}", err);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let submitted = match submissions::load_submissions(day.number, &submissions_log_path) {
+        Ok(submitted) => submitted,
+        Err(err) => {
+            eprintln!("Failed to load submissions for day {}: {}", day.number, err);
+            return ExitCode::FAILURE;
+        }
+    };
 
-            let input = input::retrieve_input(day, cookie_opt.as_deref(), &inputs_cache_path)?;
-            let now = Instant::now();
-            let result = day_func(&input)?;
+    if let Err(refusal) = submissions::check_answer(part, answer, &submitted) {
+        eprintln!(
+            "Not submitting {} for day {} {}: {}",
+            answer,
+            day.number,
+            output::part_name(part).to_lowercase(),
+            refusal
+        );
+        return ExitCode::FAILURE;
+    }
 
-            Ok((result, now.elapsed()))
+    println!(
+        "Submitting {} for day {} {}...",
+        answer,
+        day.number,
+        output::part_name(part).to_lowercase()
+    );
+
+    let outcome =
+        match submit::submit_answer(context.client, context.year, day.number, part, answer) {
+            Ok(outcome) => outcome,
+            Err(err) => {
+                eprintln!("Failed to submit the answer: {}", err);
+                return ExitCode::FAILURE;
+            }
+        };
+
+    println!("{}", outcome);
+
+    let submission = Submission::new(part, answer, outcome.clone());
+
+    if let Err(err) = submissions::log_submission(day.number, &submissions_log_path, &submission) {
+        eprintln!(
+            "Failed to log the submission for day {}: {}",
+            day.number, err
+        );
+    }
+
+    match outcome {
+        SubmitOutcome::Correct => {
+            let mut answers = expected.unwrap_or_default();
+
+            match part {
+                Part::One => answers.part1 = Some(answer.clone()),
+                Part::Two => answers.part2 = Some(answer.clone()),
+            }
+
+            if let Err(err) =
+                answers::save_answers(day.number, context.answers_store_path, &answers)
+            {
+                eprintln!("Failed to save answers for day {}: {}", day.number, err);
+            }
+
+            ExitCode::SUCCESS
+        }
+        SubmitOutcome::AlreadySolved => ExitCode::SUCCESS,
+        _ => ExitCode::FAILURE,
+    }
+}
+
+/// Run every day, and write the table of their results into the file at `path`.
+fn write_report(days: &[Day], context: &RunContext, path: &Path) -> ExitCode {
+    let contents = match fs::read_to_string(path) {
+        Ok(contents) => contents,
+        Err(err) => {
+            eprintln!("Failed to read {}: {}", path.display(), err);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let reports = days
+        .iter()
+        .map(|day_solution| {
+            eprintln!("Running day {}...", day_solution.number);
+
+            let task = Task {
+                day_solution,
+                example: None,
+            };
+
+            solve_day(&task, context).report
         })
-        .for_each(|result| match result {
-            Ok(((part1, part2), elapsed)) => {
-                println!("Part 1 :: {}", part1);
-                println!("Part 2 :: {}", part2);
-                println!("Took {}µs", elapsed.as_micros());
-            }
-            Err(err) => println!("Error! {:#?}", err),
-        });
+        .collect::<Vec<_>>();
+
+    let updated = match report::update_section(&contents, &report::render_table(&reports)) {
+        Ok(updated) => updated,
+        Err(err) => {
+            eprintln!("Failed to update {}: {}", path.display(), err);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    if let Err(err) = fs::write(path, updated) {
+        eprintln!("Failed to write {}: {}", path.display(), err);
+        return ExitCode::FAILURE;
+    }
+
+    eprintln!("Updated the results in {}", path.display());
+
+    ExitCode::SUCCESS
+}
+
+/// What's needed to run any one day.
+struct RunContext<'a> {
+    year: u16,
+    parts: &'a [Part],
+    bench_options: Option<BenchOptions>,
+    timeout: Option<Duration>,
+    /// Input to run the day on instead of its own, in which case its answers aren't checked.
+    input_override: Option<&'a (String, InputSource)>,
+    client: &'a AocClient,
+    inputs_cache_path: &'a Path,
+    answers_store_path: &'a Path,
+    /// Download inputs again, even if they're cached.
+    refresh: bool,
+}
+
+/// A single run of a day's solution, on either its real input or one of its examples.
+struct Task<'a> {
+    day_solution: &'a Day,
+    /// The example to run on, and its number, counting from 1.
+    example: Option<(usize, &'a Example)>,
+}
+
+/// The report for a day that has been run, along with the answers it was checked against.
+struct SolvedDay {
+    report: DayReport,
+    expected: Option<ExpectedAnswers>,
+}
+
+/// Retrieve the input for a day and run its solution on it. Nothing is printed other than
+/// progress, so that this can be done for many days at once.
+fn solve_day(task: &Task, context: &RunContext) -> SolvedDay {
+    let day = task.day_solution.number;
+    let example_number = task.example.map(|(number, _)| number);
+
+    let (input, input_source, params, expected) = match (task.example, context.input_override) {
+        (Some((_, example)), _) => (
+            example.input.to_string(),
+            InputSource::Example,
+            example.params,
+            Some(ExpectedAnswers::from(example)),
+        ),
+        (None, Some((input, input_source))) => (input.clone(), *input_source, None, None),
+        (None, None) => {
+            let expected = match answers::load_answers(day, context.answers_store_path) {
+                Ok(expected) => expected,
+                Err(err) => {
+                    eprintln!("Failed to load expected answers for day {}: {}", day, err);
+                    None
+                }
+            };
+
+            match input::retrieve_input(
+                context.client,
+                context.year,
+                day,
+                context.inputs_cache_path,
+                context.refresh,
+            ) {
+                Ok((input, input_source)) => (input, input_source, None, expected),
+                Err(err) => {
+                    return SolvedDay {
+                        report: DayReport {
+                            year: context.year,
+                            day,
+                            example: None,
+                            input_source: None,
+                            outcome: Err(DayError::Input(err)),
+                        },
+                        expected,
+                    }
+                }
+            }
+        }
+    };
+
+    // Examples are only run for the parts the puzzle gives an answer for.
+    let parts = context
+        .parts
+        .iter()
+        .copied()
+        .filter(|&part| {
+            task.example
+                .is_none_or(|(_, example)| example.answer(part).is_some())
+        })
+        .collect::<Vec<_>>();
+
+    let outcome = match context.timeout {
+        Some(timeout) => runner::run_day_with_timeout(
+            *task.day_solution,
+            input,
+            params,
+            parts,
+            expected.clone(),
+            context.bench_options,
+            timeout,
+        )
+        .map_or(Err(DayError::Timeout(timeout)), |outcome| {
+            outcome.map_err(DayError::Parse)
+        }),
+        None => runner::run_day(
+            task.day_solution,
+            &input,
+            params,
+            &parts,
+            expected.as_ref(),
+            context.bench_options,
+        )
+        .map_err(DayError::Parse),
+    };
+
+    SolvedDay {
+        report: DayReport {
+            year: context.year,
+            day,
+            example: example_number,
+            input_source: Some(input_source),
+            outcome,
+        },
+        expected,
+    }
+}
+
+/// Record the answers from a run as the expected ones, keeping any already recorded for parts that
+/// weren't run, that failed, or that haven't been solved yet.
+fn save_answers(
+    day: usize,
+    run: &DayRun,
+    expected: Option<ExpectedAnswers>,
+    answers_store_path: &Path,
+) {
+    let mut answers = expected.unwrap_or_default();
+
+    for part_run in &run.parts {
+        let Ok(answer) = &part_run.outcome else {
+            continue;
+        };
+
+        if answer == utils::NOT_YET_IMPLEMENTED {
+            continue;
+        }
+
+        match part_run.part {
+            Part::One => answers.part1 = Some(answer.clone()),
+            Part::Two => answers.part2 = Some(answer.clone()),
+        }
+    }
+
+    if let Err(err) = answers::save_answers(day, answers_store_path, &answers) {
+        eprintln!("Failed to save answers for day {}: {}", day, err);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_parts_by_number() {
+        let part = |args: &[&str]| {
+            Cli::try_parse_checked_from(["advent-of-code-2024"].iter().chain(args))
+                .map(|cli| cli.run_args.part.map(|PartArg(part)| part))
+        };
+
+        Cli::command().debug_assert();
+        assert_eq!(part(&["--part", "2"]).unwrap(), Some(Part::Two));
+        assert_eq!(part(&["-p", "1"]).unwrap(), Some(Part::One));
+        assert_eq!(part(&[]).unwrap(), None);
+        assert!(part(&["--part", "3"]).is_err());
+    }
+
+    #[test]
+    fn takes_global_options_either_side_of_subcommands() {
+        let parse = |args: &[&str]| {
+            Cli::try_parse_checked_from(["advent-of-code-2024"].iter().chain(args))
+                .map_err(|err| err.kind())
+        };
+
+        let cli = parse(&["--year", "2023", "new", "1"]).unwrap();
+        assert_eq!(cli.year, Some(2023));
+        assert!(matches!(cli.command, Some(Command::New { day: 1, .. })));
+
+        assert!(parse(&["read", "1", "--refresh"]).unwrap().refresh);
+        assert!(parse(&["--user-agent", "x", "compare"]).is_ok());
+        assert!(parse(&["--run", "1", "--refresh"]).is_ok());
+
+        assert_eq!(
+            parse(&["--run", "1", "report", "README.md"]).err(),
+            Some(ErrorKind::ArgumentConflict)
+        );
+        assert_eq!(
+            parse(&["--refresh", "--input", "-"]).err(),
+            Some(ErrorKind::ArgumentConflict)
+        );
+    }
 }
@@ -0,0 +1,145 @@
+//! Counting the allocations made by each step of a day, to find the days allocating more than they
+//! need to.
+//!
+//! Once installed as the global allocator, every allocation goes through [CountingAllocator], but it
+//! only counts them once [enable] has been called, so that runs not interested in memory pay as
+//! little as possible for it. The counts are shared by every thread, so steps can only be measured
+//! properly one at a time.
+
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    sync::atomic::{AtomicBool, AtomicIsize, AtomicU64, Ordering},
+};
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+static ALLOCATIONS: AtomicU64 = AtomicU64::new(0);
+static ALLOCATED_BYTES: AtomicU64 = AtomicU64::new(0);
+
+/// Allocations made before counting was enabled aren't known about, so freeing them can take this
+/// below zero. Only the differences between readings are meaningful.
+static LIVE_BYTES: AtomicIsize = AtomicIsize::new(0);
+static PEAK_LIVE_BYTES: AtomicIsize = AtomicIsize::new(0);
+
+/// Wraps the system allocator, keeping count of what's allocated once enabled.
+pub struct CountingAllocator;
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc(layout);
+
+        if !ptr.is_null() {
+            record_alloc(layout.size());
+        }
+
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc_zeroed(layout);
+
+        if !ptr.is_null() {
+            record_alloc(layout.size());
+        }
+
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        record_dealloc(layout.size());
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_ptr = System.realloc(ptr, layout, new_size);
+
+        // Growing a vector is as much an allocation as creating it was, so count it as freeing the
+        // old block and allocating the new one.
+        if !new_ptr.is_null() {
+            record_dealloc(layout.size());
+            record_alloc(new_size);
+        }
+
+        new_ptr
+    }
+}
+
+fn record_alloc(size: usize) {
+    if !ENABLED.load(Ordering::Relaxed) {
+        return;
+    }
+
+    ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+    ALLOCATED_BYTES.fetch_add(size as u64, Ordering::Relaxed);
+
+    let live = LIVE_BYTES.fetch_add(size as isize, Ordering::Relaxed) + size as isize;
+    PEAK_LIVE_BYTES.fetch_max(live, Ordering::Relaxed);
+}
+
+fn record_dealloc(size: usize) {
+    if !ENABLED.load(Ordering::Relaxed) {
+        return;
+    }
+
+    LIVE_BYTES.fetch_sub(size as isize, Ordering::Relaxed);
+}
+
+/// Start counting allocations.
+pub fn enable() {
+    ENABLED.store(true, Ordering::SeqCst);
+}
+
+pub fn is_enabled() -> bool {
+    ENABLED.load(Ordering::SeqCst)
+}
+
+/// What a step allocated while it ran.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct MemStats {
+    pub allocations: u64,
+    pub allocated_bytes: u64,
+    /// The most memory the step had allocated at once, on top of what was allocated before it
+    /// started.
+    pub peak_live_bytes: u64,
+}
+
+/// Run `f`, and count what it allocates, if counting is enabled.
+pub fn measure<T>(f: impl FnOnce() -> T) -> (T, Option<MemStats>) {
+    if !is_enabled() {
+        return (f(), None);
+    }
+
+    let allocations = ALLOCATIONS.load(Ordering::SeqCst);
+    let allocated_bytes = ALLOCATED_BYTES.load(Ordering::SeqCst);
+    let live_bytes = LIVE_BYTES.load(Ordering::SeqCst);
+    PEAK_LIVE_BYTES.store(live_bytes, Ordering::SeqCst);
+
+    let value = f();
+
+    let stats = MemStats {
+        allocations: ALLOCATIONS.load(Ordering::SeqCst) - allocations,
+        allocated_bytes: ALLOCATED_BYTES.load(Ordering::SeqCst) - allocated_bytes,
+        peak_live_bytes: (PEAK_LIVE_BYTES.load(Ordering::SeqCst) - live_bytes).max(0) as u64,
+    };
+
+    (value, Some(stats))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn measures_allocations() {
+        enable();
+
+        let (buffer, stats) = measure(|| vec![0u8; 4096]);
+        let stats = stats.unwrap();
+
+        assert_eq!(buffer.len(), 4096);
+        // Other tests may be allocating at the same time, so these can only be lower bounds.
+        assert!(stats.allocations >= 1);
+        assert!(stats.allocated_bytes >= 4096);
+        assert!(stats.peak_live_bytes >= 4096);
+    }
+}
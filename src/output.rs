@@ -0,0 +1,852 @@
+//! Presenting the results of running each day, either as text for us to read, or as records for
+//! other tools to consume.
+
+use std::{
+    error::Error,
+    fmt::{self, Write},
+    io::{self, Stdout},
+    time::Duration,
+};
+
+use serde::Serialize;
+
+use crate::{
+    answers::Verdict,
+    bench::{Stats, Total},
+    days::{Day, Part},
+    input::{InputSource, RetrieveInputError},
+    mem::MemStats,
+    runner::{DayRun, Failure, PartRun, Timing},
+};
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, clap::ValueEnum)]
+pub enum OutputFormat {
+    /// Human-readable text.
+    #[default]
+    Text,
+    /// One JSON object per line, for each day.
+    Json,
+    /// A CSV table with a header, and one row for each day.
+    Csv,
+}
+
+/// Everything we know about running a single day.
+pub struct DayReport {
+    pub year: u16,
+    pub day: usize,
+    /// The number of the example the day was run on, if it wasn't run on its real input.
+    pub example: Option<usize>,
+    pub input_source: Option<InputSource>,
+    pub outcome: Result<DayRun, DayError>,
+}
+
+/// Why a day failed to run at all.
+#[derive(Debug)]
+pub enum DayError {
+    /// We couldn't get hold of the input data for the day.
+    Input(RetrieveInputError),
+    /// The day's solution failed to parse the input.
+    Parse(Failure),
+    /// The day's solution didn't finish within the timeout given.
+    Timeout(Duration),
+}
+
+impl DayError {
+    fn source_name(&self) -> &'static str {
+        match self {
+            DayError::Input(_) => "input",
+            DayError::Parse(_) => "parse",
+            DayError::Timeout(_) => "solution",
+        }
+    }
+
+    fn record(&self) -> ErrorRecord {
+        match self {
+            DayError::Input(error) => ErrorRecord {
+                source: self.source_name(),
+                kind: error.kind(),
+                // This already includes the message of its cause when displayed.
+                message: error.to_string(),
+                location: None,
+            },
+            DayError::Parse(failure) => ErrorRecord::of_failure(self.source_name(), failure),
+            DayError::Timeout(timeout) => ErrorRecord {
+                source: self.source_name(),
+                kind: "timeout",
+                message: format!("Gave up after {}", humantime::format_duration(*timeout)),
+                location: None,
+            },
+        }
+    }
+}
+
+/// How the run went as a whole, once every day has been run.
+#[derive(Default)]
+pub struct Summary {
+    pub bench_total: Option<Total>,
+    pub parallel_total: Option<ParallelTotal>,
+    /// Whether any day produced the wrong answers, or failed to produce answers we know.
+    pub any_regressed: bool,
+    pub any_panicked: bool,
+    pub any_timed_out: bool,
+}
+
+impl Summary {
+    pub fn is_failure(&self) -> bool {
+        self.any_regressed || self.any_panicked || self.any_timed_out
+    }
+}
+
+/// How long running days in parallel took overall. The time each day took is measured while other
+/// days are running alongside it, so can be longer than it would take on its own.
+pub struct ParallelTotal {
+    /// The time between starting the first day and finishing the last.
+    pub wall_clock: Duration,
+    /// The CPU time the whole process used in that time, if it could be measured.
+    pub cpu: Option<Duration>,
+}
+
+/// Writes out day reports in the chosen format as they come in.
+pub struct Printer {
+    format: OutputFormat,
+    csv_writer: Option<csv::Writer<Stdout>>,
+    /// Whether to show the CPU time each day took next to its wall-clock time.
+    show_cpu_time: bool,
+}
+
+impl Printer {
+    pub fn new(format: OutputFormat) -> Self {
+        Printer {
+            format,
+            csv_writer: match format {
+                OutputFormat::Csv => Some(csv::Writer::from_writer(io::stdout())),
+                _ => None,
+            },
+            show_cpu_time: false,
+        }
+    }
+
+    /// Show the CPU time each day took as well, for when days run alongside each other and their
+    /// wall-clock times don't tell the whole story.
+    pub fn with_cpu_time(self) -> Self {
+        Printer {
+            show_cpu_time: true,
+            ..self
+        }
+    }
+
+    /// Called as a day begins running, before its input is retrieved.
+    pub fn begin_day(&mut self, day: &Day, example: Option<usize>) {
+        if self.format != OutputFormat::Text {
+            return;
+        }
+
+        let title = match day.title {
+            Some(title) => format!("Day {}: {}", day.number, title),
+            None => format!("Day {}", day.number),
+        };
+
+        println!();
+
+        match example {
+            Some(example) => println!("--- {} (example {}) ---", title, example),
+            None => println!("--- {} ---", title),
+        }
+    }
+
+    pub fn print_day(&mut self, report: &DayReport) -> Result<(), Box<dyn Error>> {
+        match self.format {
+            OutputFormat::Text => {
+                let mut text = String::new();
+                write_day_text(&mut text, report, self.show_cpu_time)?;
+                print!("{}", text);
+            }
+            OutputFormat::Json => println!("{}", serde_json::to_string(&JsonRecord::of(report))?),
+            OutputFormat::Csv => {
+                let writer = self.csv_writer.as_mut().expect("CSV writer should exist");
+
+                writer.serialize(CsvRecord::of(report))?;
+                writer.flush()?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Called once every day has been run.
+    pub fn finish(&mut self, summary: &Summary) {
+        if self.format != OutputFormat::Text {
+            return;
+        }
+
+        if let Some(total) = &summary.bench_total {
+            println!();
+            println!("--- Total ---");
+            println!("Took {}", total);
+        }
+
+        if let Some(total) = &summary.parallel_total {
+            println!();
+            println!("--- Total ---");
+            match total.cpu {
+                Some(cpu) => println!(
+                    "Took {}µs wall-clock ({}µs CPU time in total)",
+                    total.wall_clock.as_micros(),
+                    cpu.as_micros()
+                ),
+                None => println!("Took {}µs wall-clock", total.wall_clock.as_micros()),
+            }
+        }
+
+        if summary.any_regressed {
+            println!();
+            println!("Some days didn't produce their expected answers!");
+        }
+
+        if summary.any_panicked {
+            println!();
+            println!("Some days panicked!");
+        }
+
+        if summary.any_timed_out {
+            println!();
+            println!("Some days timed out!");
+        }
+    }
+}
+
+/// The results of a day as text for us to read, as printed when running days.
+pub fn day_text(report: &DayReport) -> String {
+    let mut text = String::new();
+
+    // Writing to a `String` can't fail.
+    let _ = write_day_text(&mut text, report, false);
+
+    text
+}
+
+fn write_day_text(text: &mut String, report: &DayReport, show_cpu_time: bool) -> fmt::Result {
+    let run = match &report.outcome {
+        Ok(run) => run,
+        Err(DayError::Input(error)) => return writeln!(text, "Error! {:#?}", error),
+        Err(DayError::Parse(Failure::Error(error))) => {
+            return writeln!(text, "Error parsing input! {:#?}", error)
+        }
+        Err(DayError::Parse(Failure::Panic(panic))) => {
+            return writeln!(text, "Panicked parsing input! {}", panic)
+        }
+        Err(DayError::Timeout(timeout)) => {
+            return writeln!(
+                text,
+                "TIMEOUT! Gave up after {}",
+                humantime::format_duration(*timeout)
+            )
+        }
+    };
+
+    for part_run in &run.parts {
+        match &part_run.outcome {
+            Ok(answer) => writeln!(
+                text,
+                "{} :: {}{}",
+                part_name(part_run.part),
+                answer,
+                verdict_suffix(&part_run.verdict)
+            )?,
+            Err(Failure::Error(error)) => {
+                writeln!(text, "{} :: Error! {:#?}", part_name(part_run.part), error)?
+            }
+            Err(Failure::Panic(panic)) => {
+                writeln!(text, "{} :: Panicked! {}", part_name(part_run.part), panic)?
+            }
+        }
+    }
+
+    match run.total {
+        Timing::Single(total) => {
+            let mut steps = vec![format!("parse {}µs", run.parse.elapsed().as_micros())];
+
+            steps.extend(run.parts.iter().map(|part_run| {
+                format!(
+                    "{} {}µs",
+                    part_name(part_run.part).to_lowercase(),
+                    part_run.timing.elapsed().as_micros()
+                )
+            }));
+
+            write!(text, "Took {}µs ({})", total.as_micros(), steps.join(" / "))?;
+
+            match run.cpu {
+                Some(cpu) if show_cpu_time => writeln!(text, ", {}µs CPU time", cpu.as_micros())?,
+                _ => writeln!(text)?,
+            }
+        }
+        Timing::Bench(total) => {
+            writeln!(text, "Took {}", total)?;
+
+            if let Some(cpu) = run.cpu.filter(|_| show_cpu_time) {
+                writeln!(text, "  {}µs CPU time across every run", cpu.as_micros())?;
+            }
+
+            writeln!(text, "  Parse  :: {}", timing_summary(&run.parse))?;
+
+            for part_run in &run.parts {
+                writeln!(
+                    text,
+                    "  {} :: {}",
+                    part_name(part_run.part),
+                    timing_summary(&part_run.timing)
+                )?;
+            }
+        }
+    }
+
+    if let Some(parse_mem) = run.parse_mem {
+        writeln!(text, "Memory:")?;
+        writeln!(text, "  Parse  :: {}", mem_summary(parse_mem))?;
+
+        for part_run in &run.parts {
+            if let Some(mem) = part_run.mem {
+                writeln!(
+                    text,
+                    "  {} :: {}",
+                    part_name(part_run.part),
+                    mem_summary(mem)
+                )?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+pub fn part_name(part: Part) -> &'static str {
+    match part {
+        Part::One => "Part 1",
+        Part::Two => "Part 2",
+    }
+}
+
+fn timing_summary(timing: &Timing) -> String {
+    match timing {
+        Timing::Single(elapsed) => format!("{}µs", elapsed.as_micros()),
+        Timing::Bench(stats) => stats.to_string(),
+    }
+}
+
+fn mem_summary(stats: MemStats) -> String {
+    format!(
+        "{} allocations / {} allocated / {} peak",
+        stats.allocations,
+        format_bytes(stats.allocated_bytes),
+        format_bytes(stats.peak_live_bytes)
+    )
+}
+
+fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["B", "KiB", "MiB", "GiB"];
+
+    let mut value = bytes as f64;
+    let mut unit = 0;
+
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+
+    if unit == 0 {
+        format!("{} {}", bytes, UNITS[0])
+    } else {
+        format!("{:.1} {}", value, UNITS[unit])
+    }
+}
+
+fn verdict_suffix(verdict: &Verdict) -> String {
+    match verdict {
+        Verdict::Unknown => String::new(),
+        Verdict::Correct => " ✓".to_string(),
+        Verdict::Incorrect { expected } => format!(" ✗ (expected {})", expected),
+    }
+}
+
+fn verdict_name(verdict: &Verdict) -> &'static str {
+    match verdict {
+        Verdict::Unknown => "unknown",
+        Verdict::Correct => "correct",
+        Verdict::Incorrect { .. } => "incorrect",
+    }
+}
+
+#[derive(Serialize)]
+struct JsonRecord {
+    year: u16,
+    day: usize,
+    example: Option<usize>,
+    input: Option<InputSource>,
+    parse: Option<StepRecord>,
+    part1: Option<PartRecord>,
+    part2: Option<PartRecord>,
+    total: Option<StepRecord>,
+    error: Option<ErrorRecord>,
+}
+
+#[derive(Serialize)]
+struct StepRecord {
+    elapsed_us: u64,
+    bench: Option<BenchRecord>,
+    mem: Option<MemRecord>,
+}
+
+#[derive(Serialize)]
+struct PartRecord {
+    answer: Option<String>,
+    verdict: Option<&'static str>,
+    elapsed_us: u64,
+    bench: Option<BenchRecord>,
+    mem: Option<MemRecord>,
+    error: Option<ErrorRecord>,
+}
+
+#[derive(Serialize)]
+struct MemRecord {
+    allocations: u64,
+    allocated_bytes: u64,
+    peak_live_bytes: u64,
+}
+
+#[derive(Serialize)]
+struct BenchRecord {
+    runs: usize,
+    min_us: u64,
+    median_us: u64,
+    mean_us: u64,
+    p95_us: u64,
+    stddev_us: u64,
+}
+
+#[derive(Serialize)]
+struct ErrorRecord {
+    source: &'static str,
+    kind: &'static str,
+    message: String,
+    /// Where in the source a panic happened.
+    location: Option<String>,
+}
+
+impl ErrorRecord {
+    fn of_failure(source: &'static str, failure: &Failure) -> Self {
+        ErrorRecord {
+            source,
+            kind: failure.kind(),
+            message: match failure {
+                Failure::Error(error) => error_message(error.as_ref()),
+                Failure::Panic(panic) => panic.message.clone(),
+            },
+            location: match failure {
+                Failure::Error(_) => None,
+                Failure::Panic(panic) => panic.location.clone(),
+            },
+        }
+    }
+
+    /// The message, along with the location if there is one, for formats with nowhere else to put
+    /// it.
+    fn full_message(self) -> String {
+        match self.location {
+            Some(location) => format!("{} (at {})", self.message, location),
+            None => self.message,
+        }
+    }
+}
+
+impl JsonRecord {
+    fn of(report: &DayReport) -> Self {
+        let run = report.outcome.as_ref().ok();
+        let part = |part| run.and_then(|run| run.part(part)).map(PartRecord::of);
+
+        JsonRecord {
+            year: report.year,
+            day: report.day,
+            example: report.example,
+            input: report.input_source,
+            parse: run.map(|run| StepRecord::of(&run.parse, run.parse_mem)),
+            part1: part(Part::One),
+            part2: part(Part::Two),
+            total: run.map(|run| StepRecord::of(&run.total, None)),
+            error: report.outcome.as_ref().err().map(DayError::record),
+        }
+    }
+}
+
+impl StepRecord {
+    fn of(timing: &Timing, mem: Option<MemStats>) -> Self {
+        StepRecord {
+            elapsed_us: micros(timing.elapsed()),
+            bench: match timing {
+                Timing::Single(_) => None,
+                Timing::Bench(stats) => Some(BenchRecord::of(stats)),
+            },
+            mem: mem.map(MemRecord::of),
+        }
+    }
+}
+
+impl PartRecord {
+    fn of(part_run: &PartRun) -> Self {
+        let StepRecord {
+            elapsed_us,
+            bench,
+            mem,
+        } = StepRecord::of(&part_run.timing, part_run.mem);
+
+        PartRecord {
+            answer: part_run.outcome.as_ref().ok().cloned(),
+            verdict: part_run
+                .outcome
+                .as_ref()
+                .ok()
+                .map(|_| verdict_name(&part_run.verdict)),
+            elapsed_us,
+            bench,
+            mem,
+            error: part_run
+                .outcome
+                .as_ref()
+                .err()
+                .map(|failure| ErrorRecord::of_failure("solution", failure)),
+        }
+    }
+}
+
+impl MemRecord {
+    fn of(stats: MemStats) -> Self {
+        MemRecord {
+            allocations: stats.allocations,
+            allocated_bytes: stats.allocated_bytes,
+            peak_live_bytes: stats.peak_live_bytes,
+        }
+    }
+}
+
+impl BenchRecord {
+    fn of(stats: &Stats) -> Self {
+        BenchRecord {
+            runs: stats.samples,
+            min_us: micros(stats.min),
+            median_us: micros(stats.median),
+            mean_us: micros(stats.mean),
+            p95_us: micros(stats.p95),
+            stddev_us: micros(stats.stddev),
+        }
+    }
+}
+
+/// CSV can't nest, so this is the same information as [JsonRecord], flattened out into columns.
+/// When benchmarking, the times given are the medians, and full statistics are only available as
+/// JSON.
+#[derive(Serialize)]
+struct CsvRecord {
+    year: u16,
+    day: usize,
+    example: Option<usize>,
+    input: Option<InputSource>,
+    parse_us: Option<u64>,
+    parse_allocations: Option<u64>,
+    parse_allocated_bytes: Option<u64>,
+    parse_peak_live_bytes: Option<u64>,
+    part1: Option<String>,
+    part1_verdict: Option<&'static str>,
+    part1_us: Option<u64>,
+    part1_allocations: Option<u64>,
+    part1_allocated_bytes: Option<u64>,
+    part1_peak_live_bytes: Option<u64>,
+    part1_error_kind: Option<&'static str>,
+    part1_error: Option<String>,
+    part2: Option<String>,
+    part2_verdict: Option<&'static str>,
+    part2_us: Option<u64>,
+    part2_allocations: Option<u64>,
+    part2_allocated_bytes: Option<u64>,
+    part2_peak_live_bytes: Option<u64>,
+    part2_error_kind: Option<&'static str>,
+    part2_error: Option<String>,
+    total_us: Option<u64>,
+    bench_runs: Option<usize>,
+    error_source: Option<&'static str>,
+    error_kind: Option<&'static str>,
+    error_message: Option<String>,
+}
+
+impl CsvRecord {
+    fn of(report: &DayReport) -> Self {
+        let JsonRecord {
+            year,
+            day,
+            example,
+            input,
+            parse,
+            part1,
+            part2,
+            total,
+            error,
+        } = JsonRecord::of(report);
+
+        let parse_mem = parse.as_ref().and_then(|parse| parse.mem.as_ref());
+        let part1 = FlatPart::of(part1);
+        let part2 = FlatPart::of(part2);
+
+        CsvRecord {
+            year,
+            day,
+            example,
+            input,
+            parse_us: parse.as_ref().map(|parse| parse.elapsed_us),
+            parse_allocations: parse_mem.map(|mem| mem.allocations),
+            parse_allocated_bytes: parse_mem.map(|mem| mem.allocated_bytes),
+            parse_peak_live_bytes: parse_mem.map(|mem| mem.peak_live_bytes),
+            part1: part1.answer,
+            part1_verdict: part1.verdict,
+            part1_us: part1.elapsed_us,
+            part1_allocations: part1.allocations,
+            part1_allocated_bytes: part1.allocated_bytes,
+            part1_peak_live_bytes: part1.peak_live_bytes,
+            part1_error_kind: part1.error_kind,
+            part1_error: part1.error,
+            part2: part2.answer,
+            part2_verdict: part2.verdict,
+            part2_us: part2.elapsed_us,
+            part2_allocations: part2.allocations,
+            part2_allocated_bytes: part2.allocated_bytes,
+            part2_peak_live_bytes: part2.peak_live_bytes,
+            part2_error_kind: part2.error_kind,
+            part2_error: part2.error,
+            bench_runs: total
+                .as_ref()
+                .and_then(|total| total.bench.as_ref())
+                .map(|bench| bench.runs),
+            total_us: total.map(|total| total.elapsed_us),
+            error_source: error.as_ref().map(|error| error.source),
+            error_kind: error.as_ref().map(|error| error.kind),
+            error_message: error.map(ErrorRecord::full_message),
+        }
+    }
+}
+
+/// The columns for a single part, which are all empty if it wasn't run.
+#[derive(Default)]
+struct FlatPart {
+    answer: Option<String>,
+    verdict: Option<&'static str>,
+    elapsed_us: Option<u64>,
+    allocations: Option<u64>,
+    allocated_bytes: Option<u64>,
+    peak_live_bytes: Option<u64>,
+    error_kind: Option<&'static str>,
+    error: Option<String>,
+}
+
+impl FlatPart {
+    fn of(part: Option<PartRecord>) -> Self {
+        let Some(part) = part else {
+            return FlatPart::default();
+        };
+
+        FlatPart {
+            answer: part.answer,
+            verdict: part.verdict,
+            elapsed_us: Some(part.elapsed_us),
+            allocations: part.mem.as_ref().map(|mem| mem.allocations),
+            allocated_bytes: part.mem.as_ref().map(|mem| mem.allocated_bytes),
+            peak_live_bytes: part.mem.as_ref().map(|mem| mem.peak_live_bytes),
+            error_kind: part.error.as_ref().map(|error| error.kind),
+            error: part.error.map(ErrorRecord::full_message),
+        }
+    }
+}
+
+fn micros(duration: Duration) -> u64 {
+    duration.as_micros().try_into().unwrap_or(u64::MAX)
+}
+
+/// The error's message, followed by those of everything that caused it.
+fn error_message(error: &dyn Error) -> String {
+    let mut message = error.to_string();
+    let mut source = error.source();
+
+    while let Some(cause) = source {
+        message.push_str(": ");
+        message.push_str(&cause.to_string());
+        source = cause.source();
+    }
+
+    message
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::{json, Value};
+
+    use super::*;
+    use crate::panics::Panic;
+
+    fn report(outcome: Result<DayRun, DayError>) -> DayReport {
+        DayReport {
+            year: 2024,
+            day: 3,
+            example: None,
+            input_source: Some(InputSource::Cached),
+            outcome,
+        }
+    }
+
+    fn part_run(part: Part, outcome: Result<String, Failure>, verdict: Verdict) -> PartRun {
+        PartRun {
+            part,
+            outcome,
+            verdict,
+            expected_known: true,
+            timing: Timing::Single(Duration::from_micros(20)),
+            mem: None,
+        }
+    }
+
+    fn day_run(parts: Vec<PartRun>) -> DayRun {
+        DayRun {
+            parse: Timing::Single(Duration::from_micros(10)),
+            parse_mem: None,
+            parts,
+            total: Timing::Single(Duration::from_micros(50)),
+            cpu: None,
+        }
+    }
+
+    fn json(report: &DayReport) -> Value {
+        serde_json::to_value(JsonRecord::of(report)).unwrap()
+    }
+
+    fn csv(reports: &[DayReport]) -> String {
+        let mut writer = csv::Writer::from_writer(Vec::new());
+
+        for report in reports {
+            writer.serialize(CsvRecord::of(report)).unwrap();
+        }
+
+        String::from_utf8(writer.into_inner().unwrap()).unwrap()
+    }
+
+    fn panicked_run() -> DayRun {
+        day_run(vec![
+            part_run(Part::One, Ok("161".to_string()), Verdict::Correct),
+            part_run(
+                Part::Two,
+                Err(Failure::Panic(Panic {
+                    message: "Invalid instruction!".to_string(),
+                    location: Some("src/days/day3.rs:40:5".to_string()),
+                })),
+                Verdict::Unknown,
+            ),
+        ])
+    }
+
+    #[test]
+    fn records_days_that_ran() {
+        assert_eq!(
+            json(&report(Ok(panicked_run()))),
+            json!({
+                "year": 2024,
+                "day": 3,
+                "example": null,
+                "input": "cached",
+                "parse": { "elapsed_us": 10, "bench": null, "mem": null },
+                "part1": {
+                    "answer": "161",
+                    "verdict": "correct",
+                    "elapsed_us": 20,
+                    "bench": null,
+                    "mem": null,
+                    "error": null,
+                },
+                "part2": {
+                    "answer": null,
+                    "verdict": null,
+                    "elapsed_us": 20,
+                    "bench": null,
+                    "mem": null,
+                    "error": {
+                        "source": "solution",
+                        "kind": "panicked",
+                        "message": "Invalid instruction!",
+                        "location": "src/days/day3.rs:40:5",
+                    },
+                },
+                "total": { "elapsed_us": 50, "bench": null, "mem": null },
+                "error": null,
+            })
+        );
+    }
+
+    #[test]
+    fn records_days_that_failed_to_run() {
+        let record = |error| json(&report(Err(error)));
+
+        for (error, expected) in [
+            (
+                DayError::Input(RetrieveInputError::NoCookieForDownload),
+                json!({
+                    "source": "input",
+                    "kind": "no_cookie",
+                    "message": "No cookie supplied to download from AOC",
+                    "location": null,
+                }),
+            ),
+            (
+                DayError::Parse(Failure::Error("Missing grid!".into())),
+                json!({
+                    "source": "parse",
+                    "kind": "error",
+                    "message": "Missing grid!",
+                    "location": null,
+                }),
+            ),
+            (
+                DayError::Timeout(Duration::from_secs(10)),
+                json!({
+                    "source": "solution",
+                    "kind": "timeout",
+                    "message": "Gave up after 10s",
+                    "location": null,
+                }),
+            ),
+        ] {
+            let record = record(error);
+
+            assert_eq!(record["error"], expected);
+
+            for step in ["parse", "part1", "part2", "total"] {
+                assert_eq!(record[step], Value::Null);
+            }
+        }
+    }
+
+    #[test]
+    fn flattens_records_into_csv() {
+        let reports = [
+            report(Ok(panicked_run())),
+            report(Err(DayError::Timeout(Duration::from_secs(10)))),
+        ];
+
+        assert_eq!(
+            csv(&reports),
+            "year,day,example,input,\
+             parse_us,parse_allocations,parse_allocated_bytes,parse_peak_live_bytes,\
+             part1,part1_verdict,part1_us,part1_allocations,part1_allocated_bytes,\
+             part1_peak_live_bytes,part1_error_kind,part1_error,\
+             part2,part2_verdict,part2_us,part2_allocations,part2_allocated_bytes,\
+             part2_peak_live_bytes,part2_error_kind,part2_error,\
+             total_us,bench_runs,error_source,error_kind,error_message\n\
+             2024,3,,cached,10,,,,161,correct,20,,,,,,,,20,,,,panicked,\
+             Invalid instruction! (at src/days/day3.rs:40:5),50,,,,\n\
+             2024,3,,cached,,,,,,,,,,,,,,,,,,,,,,,solution,timeout,Gave up after 10s\n"
+        );
+    }
+}
@@ -0,0 +1,116 @@
+//! Catching panics from days' solutions, so that one broken day can't bring down the rest of a run,
+//! and keeping hold of where they happened so that they can be reported properly.
+
+use std::{
+    any::Any,
+    cell::{Cell, RefCell},
+    error::Error,
+    fmt::Display,
+    panic::{self, AssertUnwindSafe},
+};
+
+thread_local! {
+    /// How many calls to [catch] are in progress on this thread. Panics on any other thread are
+    /// left to the hook that was there before, even while another thread is catching them.
+    static CATCHING: Cell<usize> = const { Cell::new(0) };
+
+    /// The last panic recorded by the hook on this thread, waiting to be picked up by the [catch]
+    /// that caught it.
+    static RECORDED: RefCell<Option<Panic>> = const { RefCell::new(None) };
+}
+
+/// A panic from a day's solution.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Panic {
+    pub message: String,
+    /// Where in the source the panic happened, if known.
+    pub location: Option<String>,
+}
+
+impl Display for Panic {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.location {
+            Some(location) => write!(f, "{} (at {})", self.message, location),
+            None => write!(f, "{}", self.message),
+        }
+    }
+}
+
+impl Error for Panic {}
+
+/// Replace the panic hook with one that records panics caught by [catch], rather than printing
+/// them. Panics anywhere else are left to the hook that was there before.
+pub fn install_hook() {
+    let previous_hook = panic::take_hook();
+
+    panic::set_hook(Box::new(move |info| {
+        if CATCHING.get() == 0 {
+            return previous_hook(info);
+        }
+
+        RECORDED.set(Some(Panic {
+            message: payload_message(info.payload()),
+            location: info.location().map(ToString::to_string),
+        }));
+    }));
+}
+
+/// Run `f`, catching it if it panics.
+///
+/// Whatever `f` was working on when it panicked is assumed not to be used again, as none of the
+/// days' solutions mutate anything they share.
+pub fn catch<T>(f: impl FnOnce() -> T) -> Result<T, Panic> {
+    CATCHING.set(CATCHING.get() + 1);
+    let result = panic::catch_unwind(AssertUnwindSafe(f));
+    CATCHING.set(CATCHING.get() - 1);
+
+    result.map_err(|payload| {
+        let message = payload_message(payload.as_ref());
+
+        // Panics on other threads, such as rayon's, are carried over to this one without passing
+        // through the hook here, so only their message is known.
+        RECORDED
+            .take()
+            .filter(|panic| panic.message == message)
+            .unwrap_or(Panic {
+                message,
+                location: None,
+            })
+    })
+}
+
+fn payload_message(payload: &(dyn Any + Send)) -> String {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        message.to_string()
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        "Box<dyn Any>".to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn catches_panic_with_location() {
+        install_hook();
+
+        let panic = catch(|| panic!("Invalid char in grid!")).unwrap_err();
+
+        assert_eq!(panic.message, "Invalid char in grid!");
+        assert!(panic.location.unwrap().starts_with("src/panics.rs:"));
+        assert_eq!(catch(|| 42), Ok(42));
+    }
+
+    #[test]
+    fn catches_panic_from_other_threads() {
+        install_hook();
+
+        let panic = catch(|| rayon::join(|| panic!("Invalid char in grid!"), || ())).unwrap_err();
+
+        assert_eq!(panic.message, "Invalid char in grid!");
+        assert_eq!(panic.location, None);
+    }
+}
@@ -0,0 +1,341 @@
+//! Fetching the description of each day's puzzle, caching it next to the inputs, and turning it into
+//! text that reads well in a terminal.
+
+use std::{
+    fs,
+    io::ErrorKind,
+    path::{Path, PathBuf},
+};
+
+use crate::{
+    client::AocClient,
+    input::{InputSource, RetrieveInputError},
+};
+
+/// How wide to wrap paragraphs to.
+const WRAP_WIDTH: usize = 80;
+
+/// Where the puzzle page for a day is kept in the inputs cache.
+pub fn cached_puzzle_path(inputs_cache_path: &Path, day: usize) -> PathBuf {
+    inputs_cache_path.join(format!("day_{}.html", day))
+}
+
+/// Retrieve the page describing a day's puzzle, from the cache if it's there, or from Advent of
+/// Code.
+///
+/// Part 2 is only on the page once part 1 has been solved, so once `part_1_solved`, a cached page
+/// without it is downloaded again if there's a cookie to do so with. With `refresh`, it's
+/// downloaded again whatever was cached. If downloading fails, the cached page is used.
+pub fn retrieve_puzzle(
+    client: &AocClient,
+    year: u16,
+    day: usize,
+    inputs_cache_path: &Path,
+    refresh: bool,
+    part_1_solved: bool,
+) -> Result<(String, InputSource), RetrieveInputError> {
+    let puzzle_path = cached_puzzle_path(inputs_cache_path, day);
+
+    let cached = match fs::read_to_string(&puzzle_path) {
+        Ok(page) => Some(page),
+        Err(err) if err.kind() == ErrorKind::NotFound => None,
+        Err(err) => return Err(err.into()),
+    };
+
+    if let Some(page) = &cached {
+        let out_of_date = refresh || (part_1_solved && !has_part_2(page));
+
+        // Without a cookie, the page would only have part 1 on it, however out of date this is.
+        if client.cookie().is_none() || !out_of_date {
+            return Ok((page.clone(), InputSource::Cached));
+        }
+    }
+
+    match download_puzzle(client, year, day) {
+        Ok(page) => {
+            fs::write(&puzzle_path, &page)?;
+
+            Ok((page, InputSource::Downloaded))
+        }
+        Err(err) => match cached {
+            Some(page) => {
+                client.report(&format!(
+                    "Failed to download the puzzle for {} day {}, using the cached one: {}",
+                    year, day, err
+                ));
+
+                Ok((page, InputSource::Cached))
+            }
+            None => Err(err),
+        },
+    }
+}
+
+/// Download the page for the given day's puzzle. Without a cookie, only part 1 is ever on it.
+fn download_puzzle(
+    client: &AocClient,
+    year: u16,
+    day: usize,
+) -> Result<String, RetrieveInputError> {
+    client.report(&format!("Downloading puzzle for {} day {}...", year, day));
+
+    let response = client.get(&format!("/{}/day/{}", year, day)).send()?;
+
+    client.check_rate_limit(&response)?;
+
+    Ok(response.error_for_status()?.text()?)
+}
+
+fn has_part_2(page: &str) -> bool {
+    articles(page).nth(1).is_some()
+}
+
+/// The contents of each `<article>` on the page, one for each part that's been unlocked.
+fn articles(page: &str) -> impl Iterator<Item = &str> {
+    page.split("<article").skip(1).filter_map(|rest| {
+        let (_, article) = rest.split_once('>')?;
+        let (article, _) = article.split_once("</article>")?;
+
+        Some(article)
+    })
+}
+
+/// Render the puzzle description on a page as plain text, or `None` if there isn't one on it.
+///
+/// Paragraphs are wrapped, code blocks are indented, and emphasis and inline code are marked the
+/// way Markdown would.
+pub fn render_puzzle(page: &str) -> Option<String> {
+    let rendered = articles(page).map(render_article).collect::<Vec<_>>();
+
+    (!rendered.is_empty()).then(|| rendered.concat())
+}
+
+fn render_article(article: &str) -> String {
+    let mut renderer = Renderer::default();
+    let mut rest = article;
+
+    while let Some(start) = rest.find('<') {
+        renderer.text(&decode_entities(&rest[..start]));
+
+        let Some(end) = rest[start..].find('>') else {
+            break;
+        };
+
+        renderer.tag(&rest[start + 1..start + end]);
+        rest = &rest[start + end + 1..];
+    }
+
+    renderer.text(&decode_entities(rest));
+    renderer.finish()
+}
+
+#[derive(Default)]
+struct Renderer {
+    out: String,
+    /// The text of the paragraph, heading or list item being rendered.
+    inline: String,
+    /// What to start each line of the current block with.
+    prefix: &'static str,
+    in_pre: bool,
+    in_code: bool,
+}
+
+impl Renderer {
+    fn text(&mut self, text: &str) {
+        self.inline.push_str(text);
+    }
+
+    fn tag(&mut self, tag: &str) {
+        let closing = tag.starts_with('/');
+        let name = tag
+            .trim_start_matches('/')
+            .split(|c: char| c.is_whitespace() || c == '/')
+            .next()
+            .unwrap_or_default()
+            .to_lowercase();
+
+        match (name.as_str(), closing) {
+            ("p" | "h2" | "ul", _) => self.flush(),
+            ("li", false) => {
+                self.flush();
+                self.prefix = "  - ";
+            }
+            ("li", true) => self.flush(),
+            ("pre", false) => {
+                self.flush();
+                self.in_pre = true;
+            }
+            ("pre", true) => {
+                for line in self.inline.trim_end_matches('\n').lines() {
+                    self.out.push_str("    ");
+                    self.out.push_str(line);
+                    self.out.push('\n');
+                }
+
+                self.out.push('\n');
+                self.inline.clear();
+                self.in_pre = false;
+            }
+            ("code", _) if !self.in_pre => {
+                self.in_code = !closing;
+                self.inline.push('`');
+            }
+            ("em", _) if !self.in_pre && !self.in_code => self.inline.push('*'),
+            ("br", _) => self.flush(),
+            _ => {}
+        }
+    }
+
+    /// Wrap up the current block, if there is one.
+    fn flush(&mut self) {
+        let words = self.inline.split_whitespace().collect::<Vec<_>>();
+
+        if !words.is_empty() {
+            let continuation = " ".repeat(self.prefix.len());
+            let mut line = self.prefix.to_string();
+            let mut line_empty = true;
+
+            for word in words {
+                if !line_empty && line.len() + 1 + word.len() > WRAP_WIDTH {
+                    self.out.push_str(&line);
+                    self.out.push('\n');
+                    line = continuation.clone();
+                    line_empty = true;
+                }
+
+                if !line_empty {
+                    line.push(' ');
+                }
+
+                line.push_str(word);
+                line_empty = false;
+            }
+
+            self.out.push_str(&line);
+            self.out.push_str("\n\n");
+        }
+
+        self.inline.clear();
+        self.prefix = "";
+    }
+
+    fn finish(mut self) -> String {
+        self.flush();
+        self.out
+    }
+}
+
+/// The contents of every code block in the puzzle description, which is where the examples are
+/// given.
+pub fn example_blocks(page: &str) -> Vec<String> {
+    articles(page)
+        .flat_map(|article| article.split("<pre><code>").skip(1))
+        .filter_map(|rest| rest.split_once("</code></pre>"))
+        .map(|(block, _)| decode_entities(&strip_tags(block)))
+        .collect()
+}
+
+/// Some text without any tags, such as those highlighting parts of an example.
+pub fn strip_tags(html: &str) -> String {
+    let mut text = String::new();
+    let mut in_tag = false;
+
+    for c in html.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' => in_tag = false,
+            c if !in_tag => text.push(c),
+            _ => {}
+        }
+    }
+
+    text
+}
+
+fn decode_entities(text: &str) -> String {
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&apos;", "'")
+        .replace("&nbsp;", " ")
+        .replace("&amp;", "&")
+}
+
+#[cfg(test)]
+mod tests {
+    use indoc::indoc;
+
+    use super::*;
+    use crate::{client::ClientConfig, support::temp_dir::TempDir};
+
+    const PAGE: &str = indoc! {r#"
+        <!DOCTYPE html>
+        <html lang="en-us">
+        <body>
+        <main>
+        <article class="day-desc"><h2>--- Day 1: Historian Hysteria ---</h2>
+        <p>For example:</p>
+        <pre><code>3   4
+        4   3
+        2   5
+        </code></pre>
+        <p>Pair up the <em>smallest number in the left list</em> with the smallest in the right.</p>
+        <pre><code><em>3</em> &lt; 4
+        </code></pre>
+        </article>
+        <p>Your puzzle answer was <code>11</code>.</p>
+        </main>
+        </body>
+        </html>
+    "#};
+
+    #[test]
+    fn renders_puzzles() {
+        let page = "<html><body><main>\
+            <article class=\"day-desc\"><h2>--- Day 1: Test ---</h2>\
+            <p>Find the <em>total distance</em> between &lt;lists&gt;:</p>\
+            <pre><code>3   4\n4   3\n</code></pre>\
+            <ul><li>The answer is <code><em>11</em></code>.</li></ul></article>\
+            <p>Your puzzle answer was <code>11</code>.</p>\
+            <article class=\"day-desc\"><h2 id=\"part2\">--- Part Two ---</h2>\
+            <p>Now something else.</p></article>\
+            </main></body></html>";
+
+        assert_eq!(
+            render_puzzle(page).unwrap(),
+            "--- Day 1: Test ---\n\n\
+             Find the *total distance* between <lists>:\n\n\
+             \x20   3   4\n\
+             \x20   4   3\n\n\
+             \x20 - The answer is `11`.\n\n\
+             --- Part Two ---\n\n\
+             Now something else.\n\n"
+        );
+        assert!(has_part_2(page));
+        assert_eq!(render_puzzle("<html></html>"), None);
+    }
+
+    #[test]
+    fn finds_examples_in_cached_puzzles() {
+        let cache = TempDir::new("puzzle-cache");
+        fs::write(cached_puzzle_path(cache.path(), 1), PAGE).unwrap();
+
+        // Nowhere to download from, so this would fail if it didn't use the cache.
+        let client = AocClient::new(
+            &ClientConfig {
+                base_url: "http://127.0.0.1:9".to_string(),
+                ..ClientConfig::default()
+            },
+            None,
+        )
+        .unwrap();
+        let (page, source) = retrieve_puzzle(&client, 2024, 1, cache.path(), false, false).unwrap();
+
+        assert_eq!(source, InputSource::Cached);
+        assert_eq!(example_blocks(&page), ["3   4\n4   3\n2   5\n", "3 < 4\n"]);
+        assert!(render_puzzle(&page)
+            .unwrap()
+            .contains("Pair up the *smallest number in the left list* with"));
+    }
+}
@@ -0,0 +1,280 @@
+//! Rendering the results of a run as a Markdown table of which days are solved and how fast they
+//! run, and keeping a section of a file like the README up to date with it.
+
+use std::{error::Error, fmt::Display, time::Duration};
+
+use crate::{
+    answers::Verdict,
+    days::Part,
+    output::{part_name, DayError, DayReport},
+    runner::PartRun,
+    utils::NOT_YET_IMPLEMENTED,
+};
+
+/// The markers around the section of a file that holds the table. Anything between them is
+/// replaced each time the report is written.
+pub const SECTION_START: &str = "<!-- results start -->";
+pub const SECTION_END: &str = "<!-- results end -->";
+
+/// Render the results for each day as a Markdown table.
+pub fn render_table(reports: &[DayReport]) -> String {
+    let mut table = String::new();
+
+    table.push_str("| Day | Part 1 | Part 2 | Time | Notes |\n");
+    table.push_str("|----:|:------:|:------:|-----:|-------|\n");
+
+    for report in reports {
+        let url = format!(
+            "https://adventofcode.com/{}/day/{}",
+            report.year, report.day
+        );
+        let mut notes = Vec::new();
+
+        let (stars, time) = match &report.outcome {
+            Ok(run) => {
+                let stars = Part::ALL.map(|part| match run.part(part) {
+                    Some(part_run) => {
+                        if is_not_yet_implemented(part_run) {
+                            notes.push(format!("{} not yet implemented", part_name(part)));
+                        }
+
+                        part_status(part_run)
+                    }
+                    None => "",
+                });
+
+                (stars, format_duration(run.total.elapsed()))
+            }
+            Err(error) => {
+                notes.push(match error {
+                    DayError::Input(_) => "No input".to_string(),
+                    DayError::Parse(failure) => {
+                        format!("Failed to parse input ({})", failure.kind())
+                    }
+                    DayError::Timeout(_) => "Timed out".to_string(),
+                });
+
+                (["", ""], String::new())
+            }
+        };
+
+        table.push_str(&format!(
+            "| [{}]({}) | {} | {} | {} | {} |\n",
+            report.day,
+            url,
+            stars[0],
+            stars[1],
+            time,
+            notes.join(", ")
+        ));
+    }
+
+    table
+}
+
+fn is_not_yet_implemented(part_run: &PartRun) -> bool {
+    part_run
+        .outcome
+        .as_ref()
+        .is_ok_and(|answer| answer == NOT_YET_IMPLEMENTED)
+}
+
+/// The status of a part: a star if its answer is known to be right, or a mark for if it might not
+/// be.
+fn part_status(part_run: &PartRun) -> &'static str {
+    if is_not_yet_implemented(part_run) {
+        return "";
+    }
+
+    match (&part_run.outcome, &part_run.verdict) {
+        (Err(_), _) => "✗",
+        (Ok(_), Verdict::Correct) => "⭐",
+        (Ok(_), Verdict::Incorrect { .. }) => "✗",
+        (Ok(_), Verdict::Unknown) => "?",
+    }
+}
+
+fn format_duration(duration: Duration) -> String {
+    let micros = duration.as_micros();
+
+    if micros < 1000 {
+        format!("{} µs", micros)
+    } else if micros < 1_000_000 {
+        format!("{:.2} ms", micros as f64 / 1000.0)
+    } else {
+        format!("{:.2} s", duration.as_secs_f64())
+    }
+}
+
+/// The file to write the report into doesn't have a section marked out for it.
+#[derive(Debug)]
+pub struct MissingSectionError;
+
+impl Display for MissingSectionError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "No section marked for the results! Add a line with {} and one with {} where they \
+             should go",
+            SECTION_START, SECTION_END
+        )
+    }
+}
+
+impl Error for MissingSectionError {}
+
+/// Replace whatever is in the marked section of `contents` with `table`, leaving the rest as it
+/// was.
+pub fn update_section(contents: &str, table: &str) -> Result<String, MissingSectionError> {
+    let start = contents.find(SECTION_START).ok_or(MissingSectionError)? + SECTION_START.len();
+    let end = contents[start..]
+        .find(SECTION_END)
+        .ok_or(MissingSectionError)?
+        + start;
+
+    Ok(format!(
+        "{}\n\n{}\n{}",
+        &contents[..start],
+        table,
+        &contents[end..]
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use indoc::indoc;
+
+    use super::*;
+    use crate::{
+        panics::Panic,
+        runner::{DayRun, Failure, Timing},
+    };
+
+    fn report(day: usize, outcome: Result<DayRun, DayError>) -> DayReport {
+        DayReport {
+            year: 2024,
+            day,
+            example: None,
+            input_source: None,
+            outcome,
+        }
+    }
+
+    fn day_run(total_micros: u64, parts: Vec<(Part, Result<&str, Failure>, Verdict)>) -> DayRun {
+        DayRun {
+            parse: Timing::Single(Duration::from_micros(1)),
+            parse_mem: None,
+            parts: parts
+                .into_iter()
+                .map(|(part, outcome, verdict)| PartRun {
+                    part,
+                    outcome: outcome.map(str::to_string),
+                    verdict,
+                    expected_known: true,
+                    timing: Timing::Single(Duration::from_micros(1)),
+                    mem: None,
+                })
+                .collect(),
+            total: Timing::Single(Duration::from_micros(total_micros)),
+            cpu: None,
+        }
+    }
+
+    #[test]
+    fn renders_a_row_for_each_day() {
+        let incorrect = || Verdict::Incorrect {
+            expected: "2".to_string(),
+        };
+        let panic = Failure::Panic(Panic {
+            message: "Out of bounds!".to_string(),
+            location: None,
+        });
+
+        let reports = [
+            report(
+                1,
+                Ok(day_run(
+                    50,
+                    vec![
+                        (Part::One, Ok("11"), Verdict::Correct),
+                        (Part::Two, Ok("31"), Verdict::Correct),
+                    ],
+                )),
+            ),
+            report(
+                2,
+                Ok(day_run(
+                    1500,
+                    vec![
+                        (Part::One, Ok("1"), incorrect()),
+                        (Part::Two, Ok("4"), Verdict::Unknown),
+                    ],
+                )),
+            ),
+            // The placeholder is neither a star nor a wrong answer, just a note.
+            report(
+                3,
+                Ok(day_run(
+                    2_500_000,
+                    vec![
+                        (Part::One, Ok("161"), Verdict::Correct),
+                        (Part::Two, Ok(NOT_YET_IMPLEMENTED), Verdict::Unknown),
+                    ],
+                )),
+            ),
+            report(
+                4,
+                Ok(day_run(20, vec![(Part::One, Err(panic), Verdict::Unknown)])),
+            ),
+            report(5, Err(DayError::Timeout(Duration::from_secs(1)))),
+        ];
+
+        assert_eq!(
+            render_table(&reports),
+            indoc! {"
+                | Day | Part 1 | Part 2 | Time | Notes |
+                |----:|:------:|:------:|-----:|-------|
+                | [1](https://adventofcode.com/2024/day/1) | ⭐ | ⭐ | 50 µs |  |
+                | [2](https://adventofcode.com/2024/day/2) | ✗ | ? | 1.50 ms |  |
+                | [3](https://adventofcode.com/2024/day/3) | ⭐ |  | 2.50 s | Part 2 not yet implemented |
+                | [4](https://adventofcode.com/2024/day/4) | ✗ |  | 20 µs |  |
+                | [5](https://adventofcode.com/2024/day/5) |  |  |  | Timed out |
+            "}
+        );
+    }
+
+    #[test]
+    fn updates_only_the_marked_section() {
+        let contents = indoc! {"
+            # Advent of Code
+
+            <!-- results start -->
+            | Old | Table |
+            <!-- results end -->
+
+            Thanks for reading!
+        "};
+
+        let updated = update_section(contents, "| New | Table |\n").unwrap();
+
+        assert_eq!(
+            updated,
+            indoc! {"
+                # Advent of Code
+
+                <!-- results start -->
+
+                | New | Table |
+
+                <!-- results end -->
+
+                Thanks for reading!
+            "}
+        );
+        assert_eq!(
+            update_section(&updated, "| New | Table |\n").unwrap(),
+            updated
+        );
+        assert!(update_section("# Advent of Code\n", "").is_err());
+    }
+}
@@ -0,0 +1,528 @@
+//! Running a day's solution one step at a time, so that parsing and each part can be timed, and
+//! can succeed or fail, independently of one another.
+
+use std::{
+    error::Error,
+    fmt::Display,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        mpsc::{self, RecvTimeoutError},
+    },
+    thread,
+    time::{Duration, Instant},
+};
+
+use rayon::ThreadPoolBuilder;
+
+use crate::{
+    answers::{ExpectedAnswers, Verdict},
+    bench::{self, Stats},
+    days::{Day, Params, Part, SolutionError},
+    mem::{self, MemStats},
+    panics::{self, Panic},
+};
+
+/// How long a step took to run.
+#[derive(Debug, Clone, Copy)]
+pub enum Timing {
+    Single(Duration),
+    Bench(Stats),
+}
+
+impl Timing {
+    /// The representative time the step took. For benchmarks, this is the median.
+    pub fn elapsed(&self) -> Duration {
+        match self {
+            Timing::Single(elapsed) => *elapsed,
+            Timing::Bench(stats) => stats.median,
+        }
+    }
+
+    /// How many timed runs of the step were made.
+    pub fn samples(&self) -> usize {
+        match self {
+            Timing::Single(_) => 1,
+            Timing::Bench(stats) => stats.samples,
+        }
+    }
+}
+
+/// How many times to run each day when benchmarking.
+#[derive(Debug, Clone, Copy)]
+pub struct BenchOptions {
+    pub warmup_runs: usize,
+    pub timed_runs: usize,
+}
+
+/// Why a step of a day's solution failed to produce a result.
+#[derive(Debug)]
+pub enum Failure {
+    /// The solution returned an error.
+    Error(SolutionError),
+    /// The solution panicked.
+    Panic(Panic),
+}
+
+impl Failure {
+    /// A short, stable name for the kind of failure, for tools consuming our output.
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Failure::Error(_) => "error",
+            Failure::Panic(_) => "panicked",
+        }
+    }
+}
+
+impl Display for Failure {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Failure::Error(error) => error.fmt(f),
+            Failure::Panic(panic) => panic.fmt(f),
+        }
+    }
+}
+
+impl Error for Failure {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Failure::Error(error) => error.source(),
+            Failure::Panic(_) => None,
+        }
+    }
+}
+
+/// Run a single step of a day's solution, catching it if it panics.
+pub fn run_step<T>(step: impl FnOnce() -> Result<T, SolutionError>) -> Result<T, Failure> {
+    match panics::catch(step) {
+        Ok(Ok(value)) => Ok(value),
+        Ok(Err(error)) => Err(Failure::Error(error)),
+        Err(panic) => Err(Failure::Panic(panic)),
+    }
+}
+
+/// The results of running a day whose input parsed successfully.
+pub struct DayRun {
+    pub parse: Timing,
+    /// What parsing allocated, if we're counting allocations.
+    pub parse_mem: Option<MemStats>,
+    /// The parts that were run, in order.
+    pub parts: Vec<PartRun>,
+    /// The time taken by parsing and running every part together.
+    pub total: Timing,
+    /// The CPU time spent on the day by every thread working on it, including every run when
+    /// benchmarking, if we're measuring it. Unlike the wall-clock timings, this isn't inflated by
+    /// other days running alongside it.
+    pub cpu: Option<Duration>,
+}
+
+impl DayRun {
+    pub fn part(&self, part: Part) -> Option<&PartRun> {
+        self.parts.iter().find(|run| run.part == part)
+    }
+
+    /// Whether any part either produced the wrong answer, or failed to produce the answer we know
+    /// it should.
+    pub fn is_regression(&self) -> bool {
+        self.parts.iter().any(|run| match &run.outcome {
+            Ok(_) => run.verdict.is_incorrect(),
+            Err(_) => run.expected_known,
+        })
+    }
+
+    pub fn any_panicked(&self) -> bool {
+        self.parts
+            .iter()
+            .any(|run| matches!(run.outcome, Err(Failure::Panic(_))))
+    }
+}
+
+pub struct PartRun {
+    pub part: Part,
+    pub outcome: Result<String, Failure>,
+    /// How the answer compares to the expected one. Always unknown if the part failed.
+    pub verdict: Verdict,
+    /// Whether we know what the answer to this part should be.
+    pub expected_known: bool,
+    pub timing: Timing,
+    /// What the part allocated, if we're counting allocations.
+    pub mem: Option<MemStats>,
+}
+
+/// Parse the input for a day, and run each of the given parts on it. If parsing fails, there's
+/// nothing to run the parts on, so its failure is returned.
+///
+/// Examples may give `params` to use in place of those the day uses for the real input.
+pub fn run_day(
+    day: &Day,
+    input: &str,
+    params: Option<&Params>,
+    parts: &[Part],
+    expected: Option<&ExpectedAnswers>,
+    bench_options: Option<BenchOptions>,
+) -> Result<DayRun, Failure> {
+    let (steps, cpu) = measure_cpu(|| match bench_options {
+        Some(options) => bench::bench_day(day, input, params, parts, options),
+        None => run_day_once(day, input, params, parts),
+    });
+    let steps = steps?;
+
+    let parts = steps
+        .parts
+        .into_iter()
+        .map(
+            |PartStep {
+                 part,
+                 outcome,
+                 timing,
+                 mem,
+             }| {
+                let expected_answer = expected.and_then(|expected| expected.for_part(part));
+                let verdict = match &outcome {
+                    Ok(answer) => expected
+                        .map(|expected| expected.verify(part, answer))
+                        .unwrap_or(Verdict::Unknown),
+                    Err(_) => Verdict::Unknown,
+                };
+
+                PartRun {
+                    part,
+                    outcome,
+                    verdict,
+                    expected_known: expected_answer.is_some(),
+                    timing,
+                    mem,
+                }
+            },
+        )
+        .collect();
+
+    Ok(DayRun {
+        parse: steps.parse,
+        parse_mem: steps.parse_mem,
+        parts,
+        total: steps.total,
+        cpu,
+    })
+}
+
+static CPU_TIME_ENABLED: AtomicBool = AtomicBool::new(false);
+
+/// Start measuring the CPU time each day takes, for when days run alongside each other.
+pub fn enable_cpu_time() {
+    CPU_TIME_ENABLED.store(true, Ordering::SeqCst);
+}
+
+/// Run `f`, measuring the CPU time it takes if that's enabled.
+///
+/// Days can hand work off to rayon, and while waiting on it, a thread can pick up any other work
+/// in the pool, including other days. So that every thread's time can be put down to the day, `f`
+/// is run on a pool of its own, and the time each of its threads has used is added up at the end.
+fn measure_cpu<T: Send>(f: impl FnOnce() -> T + Send) -> (T, Option<Duration>) {
+    if !CPU_TIME_ENABLED.load(Ordering::SeqCst) {
+        return (f(), None);
+    }
+
+    let Ok(pool) = ThreadPoolBuilder::new()
+        .stack_size(WORKER_STACK_SIZE)
+        .build()
+    else {
+        return (f(), None);
+    };
+
+    // Waiting for every thread to be up and running first keeps their starting up out of the
+    // day's timings.
+    let pool_cpu_time = || {
+        pool.broadcast(|_| thread_cpu_time())
+            .into_iter()
+            .sum::<Option<Duration>>()
+    };
+
+    let start = pool_cpu_time();
+    let result = pool.install(f);
+    let cpu = start
+        .zip(pool_cpu_time())
+        .map(|(start, end)| end.saturating_sub(start));
+
+    (result, cpu)
+}
+
+/// How much CPU time the whole process has used, where the platform can tell us.
+#[cfg(unix)]
+pub fn process_cpu_time() -> Option<Duration> {
+    // SAFETY: `rusage` is plain old data, for which all zeroes is valid.
+    let mut usage = unsafe { std::mem::zeroed::<libc::rusage>() };
+
+    // SAFETY: `usage` is a valid rusage for the call to write to.
+    let result = unsafe { libc::getrusage(libc::RUSAGE_SELF, &mut usage) };
+
+    let duration = |time: libc::timeval| {
+        Duration::new(time.tv_sec as u64, 0) + Duration::from_micros(time.tv_usec as u64)
+    };
+
+    (result == 0).then(|| duration(usage.ru_utime) + duration(usage.ru_stime))
+}
+
+#[cfg(not(unix))]
+pub fn process_cpu_time() -> Option<Duration> {
+    None
+}
+
+/// How much CPU time the current thread has used, where the platform can tell us.
+#[cfg(unix)]
+fn thread_cpu_time() -> Option<Duration> {
+    let mut time = libc::timespec {
+        tv_sec: 0,
+        tv_nsec: 0,
+    };
+
+    // SAFETY: `time` is a valid timespec for the call to write to.
+    let result = unsafe { libc::clock_gettime(libc::CLOCK_THREAD_CPUTIME_ID, &mut time) };
+
+    (result == 0).then(|| Duration::new(time.tv_sec as u64, time.tv_nsec as u32))
+}
+
+#[cfg(not(unix))]
+fn thread_cpu_time() -> Option<Duration> {
+    None
+}
+
+/// Stack size for the threads days are run on when they have a timeout. Some days recurse deeply
+/// enough to need as much as the main thread usually gets.
+const WORKER_STACK_SIZE: usize = 8 * 1024 * 1024;
+
+/// Like [run_day], but run on a worker thread, giving up on the day if it hasn't finished within
+/// `timeout`. If so, `None` is returned. If the thread stops without finishing the day, that's
+/// reported as a panic.
+///
+/// There's no way to stop a thread from the outside, so a day that times out is left running in
+/// the background until we exit, and may slow down the days run after it.
+pub fn run_day_with_timeout(
+    day: Day,
+    input: String,
+    params: Option<&'static Params>,
+    parts: Vec<Part>,
+    expected: Option<ExpectedAnswers>,
+    bench_options: Option<BenchOptions>,
+    timeout: Duration,
+) -> Option<Result<DayRun, Failure>> {
+    let (sender, receiver) = mpsc::channel();
+
+    thread::Builder::new()
+        .stack_size(WORKER_STACK_SIZE)
+        .spawn(move || {
+            let outcome = run_day(
+                &day,
+                &input,
+                params,
+                &parts,
+                expected.as_ref(),
+                bench_options,
+            );
+
+            // If nobody's listening any more, the day timed out, and there's nothing to do.
+            let _ = sender.send(outcome);
+        })
+        .expect("Failed to spawn a thread to run the day on!");
+
+    match receiver.recv_timeout(timeout) {
+        Ok(outcome) => Some(outcome),
+        Err(RecvTimeoutError::Timeout) => None,
+        // Only something like a panic outside of any step can stop the thread without a result.
+        Err(RecvTimeoutError::Disconnected) => Some(Err(Failure::Panic(Panic {
+            message: "The thread running the day stopped without a result!".to_string(),
+            location: None,
+        }))),
+    }
+}
+
+/// The raw results of running each step of a day, before being checked against expected answers.
+pub struct Steps {
+    pub parse: Timing,
+    pub parse_mem: Option<MemStats>,
+    pub parts: Vec<PartStep>,
+    pub total: Timing,
+}
+
+pub struct PartStep {
+    pub part: Part,
+    pub outcome: Result<String, Failure>,
+    pub timing: Timing,
+    pub mem: Option<MemStats>,
+}
+
+fn run_day_once(
+    day: &Day,
+    input: &str,
+    params: Option<&Params>,
+    parts: &[Part],
+) -> Result<Steps, Failure> {
+    let ((parsed, parse_elapsed), parse_mem) = mem::measure(|| {
+        let now = Instant::now();
+        let parsed = run_step(|| (day.parse)(input, params));
+
+        (parsed, now.elapsed())
+    });
+    let parsed = parsed?;
+
+    let parts = parts
+        .iter()
+        .map(|&part| {
+            let ((outcome, elapsed), mem) = mem::measure(|| {
+                let now = Instant::now();
+                let outcome = run_step(|| day.part(part)(&parsed));
+
+                (outcome, now.elapsed())
+            });
+
+            PartStep {
+                part,
+                outcome,
+                timing: Timing::Single(elapsed),
+                mem,
+            }
+        })
+        .collect::<Vec<_>>();
+
+    let total = parts
+        .iter()
+        .map(|step| step.timing.elapsed())
+        .sum::<Duration>()
+        + parse_elapsed;
+
+    Ok(Steps {
+        parse: Timing::Single(parse_elapsed),
+        parse_mem,
+        parts,
+        total: Timing::Single(total),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::days::Parsed;
+
+    const LOOPING_DAY: Day = Day {
+        number: 1,
+        title: None,
+        parse: |_, _| Ok(Box::new(())),
+        part1: |_| loop {
+            thread::sleep(Duration::from_millis(10));
+        },
+        part2: |_| Ok("2".to_string()),
+        examples: &[],
+    };
+
+    const QUICK_DAY: Day = Day {
+        number: 2,
+        title: None,
+        parse: |_, _| Ok(Box::new(())),
+        part1: |_| Ok("1".to_string()),
+        part2: |_| Ok("2".to_string()),
+        examples: &[],
+    };
+
+    /// Parses a number, which part 2 fails on if it's zero.
+    const NUMBER_DAY: Day = Day {
+        number: 3,
+        title: None,
+        parse: |input, _| Ok(Box::new(input.trim().parse::<u32>()?) as Parsed),
+        part1: |parsed| Ok((parsed.downcast_ref::<u32>().unwrap() + 1).to_string()),
+        part2: |parsed| match parsed.downcast_ref::<u32>().unwrap() {
+            0 => Err("Nothing to double!".into()),
+            number => Ok((number * 2).to_string()),
+        },
+        examples: &[],
+    };
+
+    /// Keeps two threads busy for 20ms of CPU time each in part 1.
+    #[cfg(unix)]
+    const PARALLEL_DAY: Day = Day {
+        number: 4,
+        title: None,
+        parse: |_, _| Ok(Box::new(())),
+        part1: |_| {
+            let spin = || {
+                let start = thread_cpu_time().unwrap();
+
+                while thread_cpu_time().unwrap() - start < Duration::from_millis(20) {}
+            };
+
+            rayon::join(spin, spin);
+
+            Ok("1".to_string())
+        },
+        part2: |_| Ok("2".to_string()),
+        examples: &[],
+    };
+
+    #[test]
+    fn runs_each_step_on_its_own() {
+        let answers = |input, parts: &[Part]| {
+            run_day(&NUMBER_DAY, input, None, parts, None, None).map(|run| {
+                run.parts
+                    .into_iter()
+                    .map(|run| (run.part, run.outcome.map_err(|failure| failure.kind())))
+                    .collect::<Vec<_>>()
+            })
+        };
+
+        assert_eq!(
+            answers("4", &Part::ALL).unwrap(),
+            [
+                (Part::One, Ok("5".to_string())),
+                (Part::Two, Ok("8".to_string()))
+            ]
+        );
+        assert_eq!(
+            answers("4", &[Part::Two]).unwrap(),
+            [(Part::Two, Ok("8".to_string()))]
+        );
+        // One part failing doesn't stop the other from being answered.
+        assert_eq!(
+            answers("0", &Part::ALL).unwrap(),
+            [(Part::One, Ok("1".to_string())), (Part::Two, Err("error"))]
+        );
+        // Without a parsed input, there's nothing to run the parts on.
+        assert!(matches!(
+            answers("four", &Part::ALL),
+            Err(Failure::Error(_))
+        ));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn measures_cpu_time_handed_off_to_rayon() {
+        enable_cpu_time();
+
+        let run = run_day(&PARALLEL_DAY, "", None, &Part::ALL, None, None).unwrap();
+
+        assert!(run.cpu.unwrap() >= Duration::from_millis(40));
+    }
+
+    #[test]
+    fn gives_up_on_days_that_time_out() {
+        let run = |day| {
+            run_day_with_timeout(
+                day,
+                String::new(),
+                None,
+                Part::ALL.to_vec(),
+                None,
+                None,
+                Duration::from_millis(100),
+            )
+        };
+
+        assert!(run(LOOPING_DAY).is_none());
+
+        let quick_run = run(QUICK_DAY).unwrap().unwrap();
+        let answers = quick_run
+            .parts
+            .iter()
+            .map(|run| run.outcome.as_ref().unwrap().as_str())
+            .collect::<Vec<_>>();
+
+        assert_eq!(answers, ["1", "2"]);
+    }
+}
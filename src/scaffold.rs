@@ -0,0 +1,237 @@
+//! Starting on a new day, by creating its module from a template, and adding the examples from the
+//! puzzle to it. Day modules register themselves, so creating the file is all it takes for the day
+//! to be run.
+
+use std::{
+    fs::{self, File},
+    io::{self, Write},
+    path::{Path, PathBuf},
+};
+
+/// Where the day modules live, relative to the root of the project.
+const DAYS_DIR_PATH: &str = "src/days";
+
+/// The year whose day modules are kept directly in the days directory, from before there were any
+/// other years. This has to match what the build script expects.
+const FLAT_LAYOUT_YEAR: u16 = 2024;
+
+/// Where the day modules for a year live, relative to the root of the project.
+pub fn days_dir(year: u16) -> PathBuf {
+    match year {
+        FLAT_LAYOUT_YEAR => PathBuf::from(DAYS_DIR_PATH),
+        year => Path::new(DAYS_DIR_PATH).join(year.to_string()),
+    }
+}
+
+const TEMPLATE: &str = include_str!("../templates/day.rs");
+
+/// How the array of examples starts in a day module.
+const EXAMPLES_START: &str = "pub(crate) const EXAMPLES: &[Example] = &[";
+
+/// The example the template starts with, without any whitespace.
+const EMPTY_EXAMPLE: &str = "Example::new(indoc!{\"\"},None,None,)";
+
+/// Render the template for a new day's module.
+pub fn render_day_module(day: usize, title: Option<&str>) -> String {
+    let registration = match title {
+        Some(title) => format!("{}, {:?}", day, title),
+        None => day.to_string(),
+    };
+
+    TEMPLATE.replace("day!(DAY)", &format!("day!({})", registration))
+}
+
+/// Create the module for a new day in `days_dir`, refusing to overwrite one that already exists.
+/// The directory is created too, if this is the first day of its year.
+pub fn create_day_module(
+    days_dir: &Path,
+    day: usize,
+    title: Option<&str>,
+) -> Result<PathBuf, io::Error> {
+    let path = days_dir.join(format!("day{}.rs", day));
+
+    fs::create_dir_all(days_dir)?;
+
+    let mut file = File::create_new(&path)?;
+    file.write_all(render_day_module(day, title).as_bytes())?;
+
+    Ok(path)
+}
+
+/// Add an example to the `EXAMPLES` of a day module's source, without any answers for it yet. The
+/// empty example the template starts with is replaced by it, as is an empty array. `None` if the
+/// module doesn't have any `EXAMPLES`.
+pub fn add_example(source: &str, input: &str) -> Option<String> {
+    let start = source.find(EXAMPLES_START)? + EXAMPLES_START.len();
+    let end = start + closing_bracket(&source[start..])?;
+    let existing = &source[start..end];
+    let example = render_example(input);
+
+    let bare_existing = existing.split_whitespace().collect::<String>();
+
+    let examples = if bare_existing.is_empty() || bare_existing == EMPTY_EXAMPLE {
+        example
+    } else if existing.starts_with('\n') {
+        format!("{}\n{},\n", existing.trim_end(), indent(&example))
+    } else {
+        format!("\n{},\n{},\n", indent(existing), indent(&example))
+    };
+
+    let mut source = format!("{}{}{}", &source[..start], examples, &source[end..]);
+
+    if !source.contains("use indoc::indoc;") {
+        source.insert_str(0, "use indoc::indoc;\n\n");
+    }
+
+    Some(source)
+}
+
+/// Add an example to the `EXAMPLES` of the module for a day in `days_dir`.
+pub fn add_example_to_day_module(
+    days_dir: &Path,
+    day: usize,
+    input: &str,
+) -> Result<PathBuf, io::Error> {
+    let path = days_dir.join(format!("day{}.rs", day));
+    let source = fs::read_to_string(&path)?;
+
+    let source = add_example(&source, input).ok_or_else(|| {
+        io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("{} doesn't have any EXAMPLES", path.display()),
+        )
+    })?;
+
+    fs::write(&path, source)?;
+
+    Ok(path)
+}
+
+/// Where the bracket closing the array that `source` is the inside of is, skipping over any in
+/// strings.
+fn closing_bracket(source: &str) -> Option<usize> {
+    let mut depth = 0;
+    let mut in_string = false;
+    let mut escaped = false;
+
+    for (index, c) in source.char_indices() {
+        match c {
+            _ if escaped => escaped = false,
+            '\\' if in_string => escaped = true,
+            '"' => in_string = !in_string,
+            _ if in_string => {}
+            '[' => depth += 1,
+            ']' if depth == 0 => return Some(index),
+            ']' => depth -= 1,
+            _ => {}
+        }
+    }
+
+    None
+}
+
+fn render_example(input: &str) -> String {
+    let mut lines = String::new();
+
+    for line in input.lines() {
+        if !line.is_empty() {
+            lines.push_str("        ");
+            lines.push_str(&line.replace('\\', "\\\\").replace('"', "\\\""));
+        }
+
+        lines.push('\n');
+    }
+
+    format!(
+        "Example::new(\n    indoc! {{\"\n{}    \"}},\n    None,\n    None,\n)",
+        lines
+    )
+}
+
+/// Indent each line of some code by another level, leaving empty lines empty.
+fn indent(code: &str) -> String {
+    code.lines()
+        .map(|line| {
+            if line.is_empty() {
+                String::new()
+            } else {
+                format!("    {}", line)
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn registers_the_new_day() {
+        assert!(render_day_module(20, None).contains("\nday!(20);\n"));
+        assert!(render_day_module(20, Some("Race Condition"))
+            .contains("\nday!(20, \"Race Condition\");\n"));
+    }
+
+    #[test]
+    fn adds_examples() {
+        let source = add_example(&render_day_module(20, None), "1 2\n\n\"3\"\n").unwrap();
+        let first = indoc::indoc! {r#"
+            pub(crate) const EXAMPLES: &[Example] = &[Example::new(
+                indoc! {"
+                    1 2
+
+                    \"3\"
+                "},
+                None,
+                None,
+            )];
+        "#};
+
+        assert!(source.contains(first));
+
+        let source = add_example(&source, "4\n").unwrap();
+        let both = indoc::indoc! {r#"
+            pub(crate) const EXAMPLES: &[Example] = &[
+                Example::new(
+                    indoc! {"
+                        1 2
+
+                        \"3\"
+                    "},
+                    None,
+                    None,
+                ),
+                Example::new(
+                    indoc! {"
+                        4
+                    "},
+                    None,
+                    None,
+                ),
+            ];
+        "#};
+
+        assert!(source.contains(both));
+    }
+
+    #[test]
+    fn adds_examples_to_empty_array() {
+        let source = add_example("pub(crate) const EXAMPLES: &[Example] = &[];\n", "1\n").unwrap();
+
+        assert_eq!(
+            source,
+            indoc::indoc! {r#"
+                use indoc::indoc;
+
+                pub(crate) const EXAMPLES: &[Example] = &[Example::new(
+                    indoc! {"
+                        1
+                    "},
+                    None,
+                    None,
+                )];
+            "#}
+        );
+    }
+}
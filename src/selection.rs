@@ -0,0 +1,250 @@
+//! Choosing which of a year's days to run, from the days, ranges of days and other criteria given on
+//! the command line.
+
+use std::{error::Error, fmt::Display, ops::RangeInclusive, str::FromStr};
+
+use crate::{
+    days::{Day, Part, Year, LAST_DAY},
+    panics,
+    utils::NOT_YET_IMPLEMENTED,
+};
+
+/// A single day, or an inclusive range of days like `1-10`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DayRange(RangeInclusive<usize>);
+
+impl DayRange {
+    fn contains(&self, day: usize) -> bool {
+        self.0.contains(&day)
+    }
+
+    fn is_single(&self) -> bool {
+        self.0.start() == self.0.end()
+    }
+}
+
+impl FromStr for DayRange {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let parse_day = |day: &str| {
+            let day = day
+                .trim()
+                .parse::<usize>()
+                .map_err(|_| format!("`{}` isn't a day number", day.trim()))?;
+
+            if !(1..=LAST_DAY).contains(&day) {
+                return Err(format!(
+                    "There is no day {}, only days 1 to {}",
+                    day, LAST_DAY
+                ));
+            }
+
+            Ok(day)
+        };
+
+        let range = match s.split_once('-') {
+            Some((start, end)) => parse_day(start)?..=parse_day(end)?,
+            None => {
+                let day = parse_day(s)?;
+                day..=day
+            }
+        };
+
+        if range.is_empty() {
+            return Err(format!("`{}` is backwards", s));
+        }
+
+        Ok(DayRange(range))
+    }
+}
+
+/// Which days to run.
+#[derive(Debug, Default)]
+pub struct Selection {
+    /// The days to run. All of them if not given.
+    pub run: Option<Vec<DayRange>>,
+    pub skip: Vec<DayRange>,
+    /// Only run the latest of the days otherwise selected.
+    pub latest: bool,
+    /// Only run days with parts that haven't been solved yet, going by whether they still give the
+    /// not-yet-implemented placeholder.
+    pub unsolved: bool,
+}
+
+impl Selection {
+    /// Pick out the days to run from those in the year, in order.
+    pub fn select(&self, year: &Year) -> Result<Vec<&'static Day>, UnknownDayError> {
+        // Ranges can span days that haven't been solved yet, but asking for one day in particular
+        // means it should be there.
+        for range in self.run.iter().flatten().filter(|range| range.is_single()) {
+            let day = *range.0.start();
+
+            if year.find_day(day).is_none() {
+                return Err(UnknownDayError {
+                    year: year.year,
+                    day,
+                });
+            }
+        }
+
+        let mut days = year
+            .days
+            .iter()
+            .filter(|day| {
+                self.run
+                    .as_ref()
+                    .is_none_or(|run| run.iter().any(|range| range.contains(day.number)))
+            })
+            .filter(|day| !self.skip.iter().any(|range| range.contains(day.number)))
+            .collect::<Vec<_>>();
+
+        if self.unsolved {
+            days.retain(|day| is_unsolved(day));
+        }
+
+        if self.latest {
+            days = days.pop().into_iter().collect();
+        }
+
+        Ok(days)
+    }
+}
+
+/// Whether any of a day's parts still give the not-yet-implemented placeholder, found by running
+/// them on the day's examples.
+///
+/// Parts are only run on examples that give their answer, as they may never finish on the others.
+/// Days whose examples don't give any answers yet, like those fresh from the template, have their
+/// parts run on the first example all the same.
+fn is_unsolved(day: &Day) -> bool {
+    let has_answers = day
+        .examples
+        .iter()
+        .any(|example| Part::ALL.iter().any(|&part| example.answer(part).is_some()));
+
+    Part::ALL.iter().any(|&part| {
+        let Some(example) = day
+            .examples
+            .iter()
+            .find(|example| !has_answers || example.answer(part).is_some())
+        else {
+            return false;
+        };
+
+        // Parts which fail or panic have been started on at least, so aren't counted as unsolved.
+        panics::catch(|| {
+            let parsed = (day.parse)(example.input, example.params).ok()?;
+            day.part(part)(&parsed).ok()
+        })
+        .ok()
+        .flatten()
+        .is_some_and(|answer| answer == NOT_YET_IMPLEMENTED)
+    })
+}
+
+/// A day was asked for that there's no solution for.
+#[derive(Debug)]
+pub struct UnknownDayError {
+    pub year: u16,
+    pub day: usize,
+}
+
+impl Display for UnknownDayError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "There is no solution for day {} of {}",
+            self.day, self.year
+        )
+    }
+}
+
+impl Error for UnknownDayError {}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        days::{self, Example},
+        utils::not_yet_implemented,
+    };
+
+    use super::*;
+
+    fn ranges(ranges: &[&str]) -> Vec<DayRange> {
+        ranges.iter().map(|range| range.parse().unwrap()).collect()
+    }
+
+    #[test]
+    fn selects_days() {
+        let year = days::find_year(2024).unwrap();
+        let numbers = |selection: Selection| {
+            selection
+                .select(year)
+                .unwrap()
+                .iter()
+                .map(|day| day.number)
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            numbers(Selection {
+                run: Some(ranges(&["1-5", "9"])),
+                skip: ranges(&["2", "4-5"]),
+                ..Selection::default()
+            }),
+            [1, 3, 9]
+        );
+        assert_eq!(
+            numbers(Selection {
+                run: Some(ranges(&["1-3"])),
+                latest: true,
+                ..Selection::default()
+            }),
+            [3]
+        );
+
+        assert!(Selection {
+            run: Some(ranges(&["25"])),
+            ..Selection::default()
+        }
+        .select(year)
+        .is_err());
+        assert!("40".parse::<DayRange>().is_err());
+        assert!("5-2".parse::<DayRange>().is_err());
+    }
+
+    #[test]
+    fn tells_unsolved_days_by_placeholder() {
+        const ANSWERED: &[Example] = &[
+            Example::new("1", Some("1"), None),
+            Example::new("2", None, Some("2")),
+        ];
+        const UNANSWERED: &[Example] = &[Example::new("", None, None)];
+
+        let day = |part2: fn(&days::Parsed) -> days::PartResult, examples| Day {
+            number: 1,
+            title: None,
+            parse: |input, _| Ok(Box::new(input.to_string())),
+            part1: |parsed| Ok(parsed.downcast_ref::<String>().unwrap().clone()),
+            part2,
+            examples,
+        };
+
+        let solved: fn(&days::Parsed) -> days::PartResult =
+            |parsed| Ok(parsed.downcast_ref::<String>().unwrap().clone());
+        // Looks unsolved unless it's only run on the second example, the one with a part 2 answer.
+        let solved_on_its_example: fn(&days::Parsed) -> days::PartResult =
+            |parsed| match parsed.downcast_ref::<String>().unwrap().as_str() {
+                "2" => Ok("2".to_string()),
+                _ => not_yet_implemented(),
+            };
+        let unsolved: fn(&days::Parsed) -> days::PartResult = |_| not_yet_implemented();
+
+        assert!(!is_unsolved(&day(solved, ANSWERED)));
+        assert!(!is_unsolved(&day(solved_on_its_example, ANSWERED)));
+        assert!(is_unsolved(&day(unsolved, ANSWERED)));
+        assert!(is_unsolved(&day(unsolved, UNANSWERED)));
+        assert!(!is_unsolved(&day(solved, UNANSWERED)));
+    }
+}
@@ -0,0 +1,244 @@
+//! A log of every answer we've submitted, so that we don't submit an answer we already know is
+//! wrong, and get locked out for it.
+//!
+//! The log is kept as JSON lines, one file per day in a directory for each year, as the site's
+//! hints about whether an answer was too high or too low aren't shown again once the page is
+//! closed.
+
+use std::{
+    error::Error,
+    fmt::Display,
+    fs::{create_dir, File, OpenOptions},
+    io::{self, BufRead, BufReader, Write},
+    path::{Path, PathBuf},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{days::Part, input, submit::SubmitOutcome, utils::NOT_YET_IMPLEMENTED};
+
+const SUBMISSIONS_DIR_PATH: &str = "submissions";
+
+/// Get the path of the submission log for the given year, creating it if need be.
+pub fn init_submissions_log(year: u16) -> Result<PathBuf, io::Error> {
+    let submissions_log_path = PathBuf::from(SUBMISSIONS_DIR_PATH);
+
+    if !submissions_log_path.exists() {
+        create_dir(&submissions_log_path)?;
+    }
+
+    input::init_year_dir(&submissions_log_path, year)
+}
+
+/// A single answer we submitted, and what the site made of it.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Submission {
+    /// When the answer was submitted, in seconds since the Unix epoch.
+    pub timestamp: u64,
+    pub part: u8,
+    pub answer: String,
+    #[serde(flatten)]
+    pub outcome: SubmitOutcome,
+}
+
+impl Submission {
+    pub fn new(part: Part, answer: &str, outcome: SubmitOutcome) -> Self {
+        Submission {
+            timestamp: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map_or(0, |elapsed| elapsed.as_secs()),
+            part: part.number(),
+            answer: answer.to_string(),
+            outcome,
+        }
+    }
+}
+
+fn log_path(submissions_log_path: &Path, day: usize) -> PathBuf {
+    submissions_log_path.join(format!("day_{}.jsonl", day))
+}
+
+/// Load everything submitted for a day, oldest first.
+pub fn load_submissions(
+    day: usize,
+    submissions_log_path: &Path,
+) -> Result<Vec<Submission>, io::Error> {
+    let file = match File::open(log_path(submissions_log_path, day)) {
+        Ok(file) => file,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(err) => return Err(err),
+    };
+
+    let mut submissions = Vec::new();
+
+    for line in BufReader::new(file).lines() {
+        let line = line?;
+
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        submissions.push(serde_json::from_str(&line)?);
+    }
+
+    Ok(submissions)
+}
+
+pub fn log_submission(
+    day: usize,
+    submissions_log_path: &Path,
+    submission: &Submission,
+) -> Result<(), io::Error> {
+    let mut line = serde_json::to_vec(submission)?;
+    line.push(b'\n');
+
+    OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(log_path(submissions_log_path, day))?
+        .write_all(&line)
+}
+
+/// Why an answer shouldn't be submitted.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Refusal {
+    /// The part hasn't been solved yet, and only gave the placeholder.
+    Placeholder,
+    Empty,
+    /// The answer looks like an error message rather than an answer.
+    ErrorText,
+    /// The same answer has been submitted before, and was wrong.
+    KnownWrong,
+    /// An answer this high or lower was already too high.
+    TooHigh {
+        bound: String,
+    },
+    /// An answer this low or higher was already too low.
+    TooLow {
+        bound: String,
+    },
+    /// The part has already been solved.
+    AlreadySolved {
+        answer: String,
+    },
+}
+
+impl Display for Refusal {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Refusal::Placeholder => write!(f, "The part hasn't been solved yet"),
+            Refusal::Empty => write!(f, "The answer is empty"),
+            Refusal::ErrorText => write!(f, "The answer looks like an error, not an answer"),
+            Refusal::KnownWrong => {
+                write!(f, "The answer has already been submitted, and was wrong")
+            }
+            Refusal::TooHigh { bound } => write!(f, "{} was already too high", bound),
+            Refusal::TooLow { bound } => write!(f, "{} was already too low", bound),
+            Refusal::AlreadySolved { answer } => {
+                write!(f, "The part has already been solved, with {}", answer)
+            }
+        }
+    }
+}
+
+impl Error for Refusal {}
+
+/// Check an answer before submitting it, against what's been submitted for the part before.
+pub fn check_answer(part: Part, answer: &str, submissions: &[Submission]) -> Result<(), Refusal> {
+    let trimmed = answer.trim();
+    let lowercase = trimmed.to_lowercase();
+
+    if trimmed == NOT_YET_IMPLEMENTED {
+        return Err(Refusal::Placeholder);
+    }
+
+    if trimmed.is_empty() {
+        return Err(Refusal::Empty);
+    }
+
+    if trimmed.contains('\n')
+        || ["error", "panicked", "failed", "not yet implemented"]
+            .iter()
+            .any(|word| lowercase.contains(word))
+    {
+        return Err(Refusal::ErrorText);
+    }
+
+    let submissions = submissions
+        .iter()
+        .filter(|submission| submission.part == part.number());
+
+    let number = trimmed.parse::<i128>().ok();
+
+    for submission in submissions {
+        let bound = submission.answer.trim().parse::<i128>().ok();
+
+        match (&submission.outcome, number, bound) {
+            (SubmitOutcome::Correct, _, _) => {
+                return Err(Refusal::AlreadySolved {
+                    answer: submission.answer.clone(),
+                })
+            }
+            (outcome, _, _) if outcome.is_wrong() && submission.answer.trim() == trimmed => {
+                return Err(Refusal::KnownWrong)
+            }
+            (SubmitOutcome::TooHigh, Some(number), Some(bound)) if number >= bound => {
+                return Err(Refusal::TooHigh {
+                    bound: submission.answer.clone(),
+                })
+            }
+            (SubmitOutcome::TooLow, Some(number), Some(bound)) if number <= bound => {
+                return Err(Refusal::TooLow {
+                    bound: submission.answer.clone(),
+                })
+            }
+            _ => {}
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn refuses_bad_answers() {
+        let submissions = [
+            Submission::new(Part::One, "100", SubmitOutcome::TooHigh),
+            Submission::new(Part::One, "20", SubmitOutcome::TooLow),
+            Submission::new(Part::One, "abc", SubmitOutcome::Wrong),
+            Submission::new(Part::Two, "7", SubmitOutcome::Correct),
+        ];
+        let check = |part, answer| check_answer(part, answer, &submissions);
+
+        assert_eq!(check(Part::One, "50"), Ok(()));
+        assert_eq!(
+            check(Part::One, "150"),
+            Err(Refusal::TooHigh {
+                bound: "100".to_string()
+            })
+        );
+        assert_eq!(
+            check(Part::One, "15"),
+            Err(Refusal::TooLow {
+                bound: "20".to_string()
+            })
+        );
+        assert_eq!(check(Part::One, "abc"), Err(Refusal::KnownWrong));
+        assert_eq!(
+            check(Part::Two, "8"),
+            Err(Refusal::AlreadySolved {
+                answer: "7".to_string()
+            })
+        );
+        assert_eq!(
+            check(Part::One, NOT_YET_IMPLEMENTED),
+            Err(Refusal::Placeholder)
+        );
+        assert_eq!(check(Part::One, " "), Err(Refusal::Empty));
+        assert_eq!(check(Part::One, "Error: no path"), Err(Refusal::ErrorText));
+    }
+}
@@ -0,0 +1,288 @@
+//! Submitting answers to Advent of Code, and making sense of what it says back.
+//!
+//! The site replies to a submission with a page whose `<article>` explains how it went, so the
+//! outcome is worked out from the wording of that.
+
+use std::{error::Error, fmt::Display, time::Duration};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    client::{AocClient, TooManyRequests},
+    days::Part,
+    puzzle::strip_tags,
+};
+
+/// What Advent of Code made of an answer we submitted.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "outcome", rename_all = "snake_case")]
+pub enum SubmitOutcome {
+    Correct,
+    /// The answer was wrong, without saying which way.
+    Wrong,
+    TooHigh,
+    TooLow,
+    /// We submitted an answer too recently, and have to wait before trying again.
+    RateLimited {
+        wait: Option<Duration>,
+    },
+    /// The part has already been solved, so the answer wasn't checked.
+    AlreadySolved,
+}
+
+impl SubmitOutcome {
+    pub fn is_wrong(&self) -> bool {
+        matches!(
+            self,
+            SubmitOutcome::Wrong | SubmitOutcome::TooHigh | SubmitOutcome::TooLow
+        )
+    }
+}
+
+impl Display for SubmitOutcome {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SubmitOutcome::Correct => write!(f, "That's the right answer!"),
+            SubmitOutcome::Wrong => write!(f, "That's not the right answer."),
+            SubmitOutcome::TooHigh => write!(f, "That's not the right answer; it's too high."),
+            SubmitOutcome::TooLow => write!(f, "That's not the right answer; it's too low."),
+            SubmitOutcome::RateLimited { wait: Some(wait) } => write!(
+                f,
+                "An answer was submitted too recently. Wait {} before trying again.",
+                humantime::format_duration(*wait)
+            ),
+            SubmitOutcome::RateLimited { wait: None } => write!(
+                f,
+                "An answer was submitted too recently. Wait a bit before trying again."
+            ),
+            SubmitOutcome::AlreadySolved => write!(f, "That part has already been solved."),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum SubmitError {
+    Network(reqwest::Error),
+    NoCookieForSubmit,
+    /// The reply didn't say anything we recognise. Holds the text of the reply.
+    UnexpectedResponse(String),
+    TooManyRequests(TooManyRequests),
+}
+
+impl Display for SubmitError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SubmitError::Network(error) => error.fmt(f),
+            SubmitError::NoCookieForSubmit => write!(f, "No cookie supplied to submit to AOC"),
+            SubmitError::UnexpectedResponse(text) => {
+                write!(f, "Didn't understand the response from AOC: {}", text)
+            }
+            SubmitError::TooManyRequests(error) => error.fmt(f),
+        }
+    }
+}
+
+impl Error for SubmitError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            SubmitError::Network(error) => Some(error),
+            SubmitError::TooManyRequests(error) => Some(error),
+            SubmitError::NoCookieForSubmit | SubmitError::UnexpectedResponse(_) => None,
+        }
+    }
+}
+
+impl From<reqwest::Error> for SubmitError {
+    fn from(value: reqwest::Error) -> Self {
+        SubmitError::Network(value)
+    }
+}
+
+impl From<TooManyRequests> for SubmitError {
+    fn from(value: TooManyRequests) -> Self {
+        SubmitError::TooManyRequests(value)
+    }
+}
+
+/// Submit an answer for a part of a day to Advent of Code.
+pub fn submit_answer(
+    client: &AocClient,
+    year: u16,
+    day: usize,
+    part: Part,
+    answer: &str,
+) -> Result<SubmitOutcome, SubmitError> {
+    if client.cookie().is_none() {
+        return Err(SubmitError::NoCookieForSubmit);
+    }
+
+    let level = part.number().to_string();
+
+    let response = client
+        .post(&format!("/{}/day/{}/answer", year, day))
+        .form(&[("level", level.as_str()), ("answer", answer)])
+        .send()?;
+
+    client.check_rate_limit(&response)?;
+
+    let page = response.error_for_status()?.text()?;
+
+    parse_response(&page)
+}
+
+/// Work out the outcome of a submission from the page sent back.
+pub fn parse_response(page: &str) -> Result<SubmitOutcome, SubmitError> {
+    let text = article_text(page);
+
+    let outcome = if text.contains("That's the right answer") {
+        SubmitOutcome::Correct
+    } else if text.contains("That's not the right answer") {
+        if text.contains("too high") {
+            SubmitOutcome::TooHigh
+        } else if text.contains("too low") {
+            SubmitOutcome::TooLow
+        } else {
+            SubmitOutcome::Wrong
+        }
+    } else if text.contains("You gave an answer too recently") {
+        SubmitOutcome::RateLimited {
+            wait: parse_wait(&text),
+        }
+    } else if text.contains("Did you already complete it?") {
+        SubmitOutcome::AlreadySolved
+    } else {
+        return Err(SubmitError::UnexpectedResponse(text));
+    };
+
+    Ok(outcome)
+}
+
+/// The text of the page's `<article>`, or of the whole page if it doesn't have one, without any
+/// tags.
+fn article_text(page: &str) -> String {
+    let article = page
+        .split_once("<article")
+        .and_then(|(_, rest)| rest.split_once('>'))
+        .and_then(|(_, rest)| rest.split_once("</article>"))
+        .map_or(page, |(article, _)| article);
+
+    strip_tags(article)
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Find how long we're told to wait, from e.g. "You have 4m 35s left to wait".
+fn parse_wait(text: &str) -> Option<Duration> {
+    let (_, rest) = text.split_once("You have ")?;
+    let (wait, _) = rest.split_once(" left to wait")?;
+
+    humantime::parse_duration(wait).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use tiny_http::{Request, Response};
+
+    use super::*;
+    use crate::{client::ClientConfig, support::stand_in::stand_in_server};
+
+    fn page(article: &str) -> String {
+        format!(
+            "<html><body><main><article><p>{}</p></article></main></body></html>",
+            article
+        )
+    }
+
+    #[test]
+    fn parses_outcomes() {
+        let outcome = |article| parse_response(&page(article)).unwrap();
+
+        assert_eq!(
+            outcome("That's the right answer!  You are <span>one gold star</span> closer."),
+            SubmitOutcome::Correct
+        );
+        assert_eq!(
+            outcome("That's not the right answer; your answer is too high.  Please wait."),
+            SubmitOutcome::TooHigh
+        );
+        assert_eq!(
+            outcome("That's not the right answer.  If you're stuck, make sure..."),
+            SubmitOutcome::Wrong
+        );
+        assert_eq!(
+            outcome(
+                "You gave an answer too recently; you have to wait after submitting an answer \
+                 before trying again.  You have 4m 35s left to wait."
+            ),
+            SubmitOutcome::RateLimited {
+                wait: Some(Duration::from_secs(4 * 60 + 35))
+            }
+        );
+        assert_eq!(
+            outcome("You don't seem to be solving the right level.  Did you already complete it?"),
+            SubmitOutcome::AlreadySolved
+        );
+        assert!(parse_response(&page("Something else entirely")).is_err());
+    }
+
+    /// Reply to each submission the way Advent of Code would, depending on the answer given.
+    fn respond_to_submission(request: &mut Request) -> Response<Cursor<Vec<u8>>> {
+        let mut body = String::new();
+        request.as_reader().read_to_string(&mut body).unwrap();
+
+        assert_eq!(request.url(), "/2024/day/1/answer");
+        assert!(request
+            .headers()
+            .iter()
+            .any(|header| header.field.equiv("Cookie") && header.value == "session=abc"));
+
+        let article = match body.as_str() {
+            "level=1&answer=11" => "That's the right answer!",
+            "level=1&answer=10" => "That's not the right answer; your answer is too low.",
+            "level=1&answer=12" => "That's not the right answer; your answer is too high.",
+            "level=2&answer=31" => "You gave an answer too recently. You have 30s left to wait.",
+            _ => "You don't seem to be solving the right level. Did you already complete it?",
+        };
+
+        Response::from_string(format!(
+            "<main><article><p>{}</p></article></main>",
+            article
+        ))
+    }
+
+    #[test]
+    fn submits_to_stand_in_server() {
+        let (base_url, server) = stand_in_server(5, respond_to_submission);
+        let client = |cookie: Option<&str>| {
+            let config = ClientConfig {
+                base_url: base_url.clone(),
+                ..ClientConfig::default()
+            };
+
+            AocClient::new(&config, cookie.map(str::to_string)).unwrap()
+        };
+        let logged_in = client(Some("session=abc"));
+        let submit = |part, answer| submit_answer(&logged_in, 2024, 1, part, answer).unwrap();
+
+        assert_eq!(submit(Part::One, "11"), SubmitOutcome::Correct);
+        assert_eq!(submit(Part::One, "10"), SubmitOutcome::TooLow);
+        assert_eq!(submit(Part::One, "12"), SubmitOutcome::TooHigh);
+        assert_eq!(
+            submit(Part::Two, "31"),
+            SubmitOutcome::RateLimited {
+                wait: Some(Duration::from_secs(30))
+            }
+        );
+        assert_eq!(submit(Part::Two, "32"), SubmitOutcome::AlreadySolved);
+
+        server.join().unwrap();
+
+        assert!(matches!(
+            submit_answer(&client(None), 2024, 1, Part::One, "11"),
+            Err(SubmitError::NoCookieForSubmit)
+        ));
+    }
+}
@@ -0,0 +1,325 @@
+//! An interactive dashboard for browsing the days, running them, and looking through their results.
+//!
+//! Days are run one at a time on a worker thread, so that the dashboard stays responsive while
+//! they run. Quitting waits for the day currently running to finish.
+
+use std::{
+    io::{self, Stdout},
+    path::Path,
+    sync::mpsc::{self, Receiver, Sender},
+    thread,
+    time::Duration,
+};
+
+use crate::{
+    client::Reporter,
+    days::{Day, Part},
+    history, input,
+    output::{self, DayError},
+    runner::PartRun,
+};
+use ratatui::{
+    backend::CrosstermBackend,
+    crossterm::{
+        event::{self, Event, KeyEventKind},
+        execute,
+        terminal::{self, EnterAlternateScreen, LeaveAlternateScreen},
+    },
+    layout::{Constraint, Layout},
+    style::{Modifier, Style, Stylize},
+    text::Line,
+    widgets::{Block, Cell, Paragraph, Row, Table, TableState, Wrap},
+    Frame, Terminal,
+};
+
+use crate::{solve_day, RunContext, Task};
+
+mod state;
+
+use state::{Dashboard, DayRow, DayState, Focus, LastPart, WorkerEvent};
+
+/// How often to check on the worker when there's no input from the user.
+const POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+const HELP: &str = "↑/↓ move · space select · a select all · enter run · tab switch pane · q quit";
+
+/// Run the dashboard until the user quits.
+pub(crate) fn run(days: &'static [Day], context: &RunContext) -> io::Result<()> {
+    let mut dashboard = Dashboard::new(load_rows(days, context));
+
+    let mut terminal = enter_terminal()?;
+
+    let result = thread::scope(|scope| {
+        let (job_sender, job_receiver) = mpsc::channel::<usize>();
+        let (event_sender, event_receiver) = mpsc::channel();
+
+        scope.spawn(move || run_worker(days, context, job_receiver, event_sender));
+
+        event_loop(&mut dashboard, &mut terminal, &job_sender, &event_receiver)
+    });
+
+    leave_terminal(&mut terminal)?;
+
+    result
+}
+
+fn enter_terminal() -> io::Result<Terminal<CrosstermBackend<Stdout>>> {
+    terminal::enable_raw_mode()?;
+    execute!(io::stdout(), EnterAlternateScreen)?;
+
+    Terminal::new(CrosstermBackend::new(io::stdout()))
+}
+
+fn leave_terminal(terminal: &mut Terminal<CrosstermBackend<Stdout>>) -> io::Result<()> {
+    terminal::disable_raw_mode()?;
+    execute!(terminal.backend_mut(), LeaveAlternateScreen)?;
+    terminal.show_cursor()
+}
+
+/// Run each day asked for in turn, until the dashboard hangs up. Anything the client reports while
+/// getting a day ready goes to the dashboard, rather than over it on the terminal.
+fn run_worker(
+    days: &[Day],
+    context: &RunContext,
+    jobs: Receiver<usize>,
+    events: Sender<WorkerEvent>,
+) {
+    for index in jobs {
+        if events.send(WorkerEvent::Started(index)).is_err() {
+            return;
+        }
+
+        let client = context.client.clone().with_reporter(Reporter::new({
+            let events = events.clone();
+            move |message| {
+                let _ = events.send(WorkerEvent::Reported(index, message.to_string()));
+            }
+        }));
+        let context = RunContext {
+            client: &client,
+            ..*context
+        };
+
+        let task = Task {
+            day_solution: &days[index],
+            example: None,
+        };
+        let report = solve_day(&task, &context).report;
+
+        if events
+            .send(WorkerEvent::Finished(index, Box::new(report)))
+            .is_err()
+        {
+            return;
+        }
+    }
+}
+
+/// Each day's row, with what's known about it from before this session.
+fn load_rows(days: &'static [Day], context: &RunContext) -> Vec<DayRow> {
+    let entries = history::load(Path::new(history::HISTORY_FILE_PATH)).unwrap_or_default();
+
+    days.iter()
+        .map(|day| {
+            let last = [1, 2].map(|part| {
+                entries
+                    .iter()
+                    .rev()
+                    .find(|entry| {
+                        (entry.year, entry.day, entry.part) == (context.year, day.number, part)
+                    })
+                    .map(|entry| LastPart {
+                        answer: entry.answer.clone(),
+                        elapsed: Duration::from_micros(entry.median_us),
+                    })
+            });
+            let input_cached =
+                input::cached_input_path(context.inputs_cache_path, day.number).exists();
+
+            DayRow::new(day, input_cached, last)
+        })
+        .collect()
+}
+
+fn event_loop(
+    dashboard: &mut Dashboard,
+    terminal: &mut Terminal<CrosstermBackend<Stdout>>,
+    jobs: &Sender<usize>,
+    events: &Receiver<WorkerEvent>,
+) -> io::Result<()> {
+    let mut table_state = TableState::default();
+
+    while !dashboard.quitting {
+        for event in events.try_iter() {
+            dashboard.handle_worker_event(event);
+        }
+
+        table_state.select(Some(dashboard.highlighted));
+        terminal.draw(|frame| draw(frame, dashboard, &mut table_state))?;
+
+        if event::poll(POLL_INTERVAL)? {
+            if let Event::Key(key) = event::read()? {
+                if key.kind == KeyEventKind::Press {
+                    dashboard.handle_key(key.code, jobs);
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn draw(frame: &mut Frame, dashboard: &Dashboard, table_state: &mut TableState) {
+    let [main_area, help_area] =
+        Layout::vertical([Constraint::Min(0), Constraint::Length(1)]).areas(frame.area());
+    let [days_area, output_area] =
+        Layout::horizontal([Constraint::Percentage(60), Constraint::Percentage(40)])
+            .areas(main_area);
+
+    let border_style = |focus| {
+        if dashboard.focus == focus {
+            Style::new().bold()
+        } else {
+            Style::new().dim()
+        }
+    };
+
+    let header = Row::new(["", "Day", "Title", "Input", "Part 1", "Part 2", "Time"]).bold();
+    let rows = dashboard.rows.iter().map(day_row).collect::<Vec<_>>();
+
+    let table = Table::new(
+        rows,
+        [
+            Constraint::Length(1),
+            Constraint::Length(3),
+            Constraint::Fill(2),
+            Constraint::Length(5),
+            Constraint::Fill(1),
+            Constraint::Fill(1),
+            Constraint::Length(10),
+        ],
+    )
+    .header(header)
+    .row_highlight_style(Style::new().add_modifier(Modifier::REVERSED))
+    .block(
+        Block::bordered()
+            .title(" Days ")
+            .border_style(border_style(Focus::Days)),
+    );
+
+    frame.render_stateful_widget(table, days_area, table_state);
+
+    let (title, text) = match dashboard.highlighted_row() {
+        Some(row) => (format!(" Day {} ", row.day.number), output_text(row)),
+        None => (" Output ".to_string(), String::new()),
+    };
+
+    let output = Paragraph::new(text)
+        .wrap(Wrap { trim: false })
+        .scroll((dashboard.output_scroll, 0))
+        .block(
+            Block::bordered()
+                .title(title)
+                .border_style(border_style(Focus::Output)),
+        );
+
+    frame.render_widget(output, output_area);
+    frame.render_widget(Line::from(HELP).dim(), help_area);
+}
+
+/// The row for a day in the table: the results from this session if it's been run, or the last
+/// ones recorded otherwise.
+fn day_row(row: &DayRow) -> Row<'static> {
+    let (parts, time) = match &row.state {
+        DayState::Done(report) => match &report.outcome {
+            Ok(run) => (
+                Part::ALL.map(|part| run.part(part).map_or(String::new(), part_summary)),
+                format!("{}µs", run.total.elapsed().as_micros()),
+            ),
+            Err(error) => (
+                [error_summary(error).to_string(), String::new()],
+                String::new(),
+            ),
+        },
+        DayState::Queued => (Default::default(), "queued".to_string()),
+        DayState::Running => (Default::default(), "running…".to_string()),
+        DayState::Idle => {
+            let parts = row.last.each_ref().map(|last| {
+                last.as_ref()
+                    .and_then(|last| last.answer.clone())
+                    .unwrap_or_default()
+            });
+            let time = row
+                .last
+                .iter()
+                .flatten()
+                .map(|last| last.elapsed)
+                .sum::<Duration>();
+
+            let time = if row.last.iter().any(Option::is_some) {
+                format!("{}µs", time.as_micros())
+            } else {
+                String::new()
+            };
+
+            (parts, time)
+        }
+    };
+
+    let [part1, part2] = parts;
+
+    Row::new([
+        Cell::from(if row.selected { "●" } else { " " }),
+        Cell::from(row.day.number.to_string()),
+        Cell::from(row.day.title.unwrap_or_default()),
+        Cell::from(if row.input_cached { "✓" } else { "·" }),
+        Cell::from(part1),
+        Cell::from(part2),
+        Cell::from(time),
+    ])
+}
+
+fn part_summary(part_run: &PartRun) -> String {
+    match &part_run.outcome {
+        Ok(answer) if part_run.verdict.is_incorrect() => format!("{} ✗", answer),
+        Ok(answer) => answer.clone(),
+        Err(failure) => failure.kind().to_string(),
+    }
+}
+
+fn error_summary(error: &DayError) -> &'static str {
+    match error {
+        DayError::Input(_) => "no input",
+        DayError::Parse(failure) => failure.kind(),
+        DayError::Timeout(_) => "timed out",
+    }
+}
+
+/// Everything about the highlighted day, for the output pane.
+fn output_text(row: &DayRow) -> String {
+    let mut text = match row.day.title {
+        Some(title) => format!("{}\n\n", title),
+        None => String::new(),
+    };
+
+    match &row.state {
+        DayState::Idle if row.last.iter().any(Option::is_some) => {
+            text.push_str("Not run yet. The answers shown are from the last recorded run.\n")
+        }
+        DayState::Idle => text.push_str("Not run yet.\n"),
+        DayState::Queued => text.push_str("Waiting to run...\n"),
+        DayState::Running => text.push_str("Running...\n"),
+        DayState::Done(_) => {}
+    }
+
+    for message in &row.messages {
+        text.push_str(message);
+        text.push('\n');
+    }
+
+    if let DayState::Done(report) = &row.state {
+        text.push_str(&output::day_text(report));
+    }
+
+    text
+}
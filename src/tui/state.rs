@@ -0,0 +1,248 @@
+//! What the dashboard is showing, and how that changes with the user's keys and the worker's
+//! progress, kept apart from drawing it so that it can be tested without a terminal.
+
+use std::{sync::mpsc::Sender, time::Duration};
+
+use ratatui::crossterm::event::KeyCode;
+
+use crate::{days::Day, output::DayReport};
+
+/// What the worker has to say about a day it's been asked to run.
+pub(super) enum WorkerEvent {
+    Started(usize),
+    /// Something happened while getting the day ready to run, like its input being downloaded.
+    Reported(usize, String),
+    Finished(usize, Box<DayReport>),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum Focus {
+    Days,
+    Output,
+}
+
+/// Where a day is up to in this session.
+pub(super) enum DayState {
+    Idle,
+    Queued,
+    Running,
+    Done(Box<DayReport>),
+}
+
+/// The answer and timing a part gave the last time it was recorded in the history.
+pub(super) struct LastPart {
+    pub(super) answer: Option<String>,
+    pub(super) elapsed: Duration,
+}
+
+pub(super) struct DayRow {
+    pub(super) day: &'static Day,
+    pub(super) input_cached: bool,
+    pub(super) last: [Option<LastPart>; 2],
+    pub(super) state: DayState,
+    /// What was reported while the day was last run, such as its input being downloaded.
+    pub(super) messages: Vec<String>,
+    pub(super) selected: bool,
+}
+
+impl DayRow {
+    pub(super) fn new(day: &'static Day, input_cached: bool, last: [Option<LastPart>; 2]) -> Self {
+        DayRow {
+            day,
+            input_cached,
+            last,
+            state: DayState::Idle,
+            messages: Vec::new(),
+            selected: false,
+        }
+    }
+}
+
+pub(super) struct Dashboard {
+    pub(super) rows: Vec<DayRow>,
+    /// The index of the row the cursor is on.
+    pub(super) highlighted: usize,
+    pub(super) focus: Focus,
+    pub(super) output_scroll: u16,
+    pub(super) quitting: bool,
+}
+
+impl Dashboard {
+    pub(super) fn new(rows: Vec<DayRow>) -> Self {
+        Dashboard {
+            rows,
+            highlighted: 0,
+            focus: Focus::Days,
+            output_scroll: 0,
+            quitting: false,
+        }
+    }
+
+    pub(super) fn handle_worker_event(&mut self, event: WorkerEvent) {
+        match event {
+            WorkerEvent::Started(index) => self.rows[index].state = DayState::Running,
+            WorkerEvent::Reported(index, message) => self.rows[index].messages.push(message),
+            WorkerEvent::Finished(index, report) => {
+                let row = &mut self.rows[index];
+
+                // Running a day downloads its input if it wasn't cached already.
+                if report.outcome.is_ok() {
+                    row.input_cached = true;
+                }
+
+                row.state = DayState::Done(report);
+            }
+        }
+    }
+
+    pub(super) fn handle_key(&mut self, code: KeyCode, jobs: &Sender<usize>) {
+        match (code, self.focus) {
+            (KeyCode::Char('q') | KeyCode::Esc, _) => self.quitting = true,
+            (KeyCode::Tab, Focus::Days) => self.focus = Focus::Output,
+            (KeyCode::Tab, Focus::Output) => self.focus = Focus::Days,
+            (KeyCode::Up | KeyCode::Char('k'), Focus::Days) => self.move_highlight(-1),
+            (KeyCode::Down | KeyCode::Char('j'), Focus::Days) => self.move_highlight(1),
+            (KeyCode::Up | KeyCode::Char('k'), Focus::Output) => self.scroll_output(-1),
+            (KeyCode::Down | KeyCode::Char('j'), Focus::Output) => self.scroll_output(1),
+            (KeyCode::PageUp, _) => self.scroll_output(-10),
+            (KeyCode::PageDown, _) => self.scroll_output(10),
+            (KeyCode::Char(' '), _) => {
+                if let Some(row) = self.rows.get_mut(self.highlighted) {
+                    row.selected = !row.selected;
+                }
+            }
+            (KeyCode::Char('a'), _) => {
+                let select = !self.rows.iter().all(|row| row.selected);
+
+                for row in &mut self.rows {
+                    row.selected = select;
+                }
+            }
+            (KeyCode::Enter | KeyCode::Char('r'), _) => self.run_selected(jobs),
+            _ => {}
+        }
+    }
+
+    pub(super) fn highlighted_row(&self) -> Option<&DayRow> {
+        self.rows.get(self.highlighted)
+    }
+
+    fn move_highlight(&mut self, by: isize) {
+        self.highlighted = self
+            .highlighted
+            .saturating_add_signed(by)
+            .min(self.rows.len().saturating_sub(1));
+        self.output_scroll = 0;
+    }
+
+    fn scroll_output(&mut self, by: i16) {
+        self.output_scroll = self.output_scroll.saturating_add_signed(by);
+    }
+
+    /// Queue up the selected days to be run, or the highlighted one if none are selected.
+    fn run_selected(&mut self, jobs: &Sender<usize>) {
+        let mut indices = (0..self.rows.len())
+            .filter(|&index| self.rows[index].selected)
+            .collect::<Vec<_>>();
+
+        if indices.is_empty() && self.highlighted < self.rows.len() {
+            indices.push(self.highlighted);
+        }
+
+        for index in indices {
+            let row = &mut self.rows[index];
+
+            if matches!(row.state, DayState::Queued | DayState::Running) {
+                continue;
+            }
+
+            if jobs.send(index).is_ok() {
+                row.state = DayState::Queued;
+                row.messages.clear();
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::mpsc;
+
+    use super::*;
+    use crate::days;
+
+    fn dashboard() -> Dashboard {
+        let year = days::find_year(2024).unwrap();
+
+        Dashboard::new(
+            year.days[..3]
+                .iter()
+                .map(|day| DayRow::new(day, false, [None, None]))
+                .collect(),
+        )
+    }
+
+    fn press(dashboard: &mut Dashboard, keys: &[KeyCode], jobs: &Sender<usize>) {
+        for &key in keys {
+            dashboard.handle_key(key, jobs);
+        }
+    }
+
+    #[test]
+    fn moves_highlight_within_the_days() {
+        let (jobs, _) = mpsc::channel();
+        let mut dashboard = dashboard();
+
+        press(&mut dashboard, &[KeyCode::Up], &jobs);
+        assert_eq!(dashboard.highlighted, 0);
+
+        press(&mut dashboard, &[KeyCode::Tab, KeyCode::PageDown], &jobs);
+        assert_eq!(dashboard.output_scroll, 10);
+
+        // Moving to another day starts its output from the top.
+        let keys = [KeyCode::Tab, KeyCode::Down, KeyCode::Down, KeyCode::Down];
+        press(&mut dashboard, &keys, &jobs);
+        assert_eq!(dashboard.highlighted, 2);
+        assert_eq!(dashboard.output_scroll, 0);
+    }
+
+    #[test]
+    fn runs_the_selected_days_or_else_the_highlighted_one() {
+        let (jobs, queued) = mpsc::channel();
+        let mut dashboard = dashboard();
+
+        press(&mut dashboard, &[KeyCode::Down, KeyCode::Enter], &jobs);
+        assert_eq!(queued.try_iter().collect::<Vec<_>>(), [1]);
+
+        // Days already waiting to run aren't queued again.
+        let keys = [KeyCode::Char('a'), KeyCode::Enter];
+        press(&mut dashboard, &keys, &jobs);
+        assert_eq!(queued.try_iter().collect::<Vec<_>>(), [0, 2]);
+        assert!(dashboard
+            .rows
+            .iter()
+            .all(|row| matches!(row.state, DayState::Queued)));
+
+        // Selecting everything when everything is selected clears the selection.
+        press(&mut dashboard, &[KeyCode::Char('a')], &jobs);
+        assert!(dashboard.rows.iter().all(|row| !row.selected));
+    }
+
+    #[test]
+    fn keeps_what_is_reported_while_a_day_runs() {
+        let (jobs, _queued) = mpsc::channel();
+        let mut dashboard = dashboard();
+
+        dashboard.handle_worker_event(WorkerEvent::Reported(0, "Stale".to_string()));
+        press(&mut dashboard, &[KeyCode::Enter], &jobs);
+        dashboard.handle_worker_event(WorkerEvent::Started(0));
+        dashboard.handle_worker_event(WorkerEvent::Reported(
+            0,
+            "Downloading input for 2024 day 1...".to_string(),
+        ));
+
+        let row = dashboard.highlighted_row().unwrap();
+        assert!(matches!(row.state, DayState::Running));
+        assert_eq!(row.messages, ["Downloading input for 2024 day 1..."]);
+    }
+}
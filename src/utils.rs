@@ -1,16 +1,21 @@
-use std::{error::Error, io};
+use std::io;
 
-pub(crate) mod boxdraw;
-pub(crate) mod direction;
-pub(crate) mod iter;
-pub(crate) mod pos;
+use crate::days::PartResult;
+
+pub mod boxdraw;
+pub mod direction;
+pub mod iter;
+pub mod pos;
+
+/// The answer given by parts that haven't been solved yet.
+pub const NOT_YET_IMPLEMENTED: &str = "Not yet implemented!";
 
 #[allow(dead_code)]
-pub(crate) fn not_yet_implemented() -> Result<String, Box<dyn Error>> {
-    Ok("Not yet implemented!".to_string())
+pub fn not_yet_implemented() -> PartResult {
+    Ok(NOT_YET_IMPLEMENTED.to_string())
 }
 
 #[allow(dead_code)]
-pub(crate) fn wait_for_user() {
+pub fn wait_for_user() {
     let _ = io::stdin().read_line(&mut String::new());
 }
@@ -0,0 +1,26 @@
+use indoc::indoc;
+
+use crate::utils::not_yet_implemented;
+
+use super::{Example, ParseResult, PartResult};
+
+day!(DAY);
+
+pub(crate) const EXAMPLES: &[Example] = &[Example::new(
+    indoc! {"
+    "},
+    None,
+    None,
+)];
+
+pub(crate) fn parse(input: &str) -> ParseResult<String> {
+    Ok(input.to_string())
+}
+
+pub(crate) fn part1(_input: &str) -> PartResult {
+    not_yet_implemented()
+}
+
+pub(crate) fn part2(_input: &str) -> PartResult {
+    not_yet_implemented()
+}
@@ -0,0 +1,52 @@
+use advent_of_code_2024::{
+    days::{self, Part},
+    utils::NOT_YET_IMPLEMENTED,
+};
+
+#[test]
+fn every_example_gives_the_puzzles_answer() {
+    for year in days::YEARS {
+        for day in year.days {
+            let day_number = day.number;
+
+            for example in day.examples {
+                let parsed = (day.parse)(example.input, example.params).unwrap();
+
+                for part in Part::ALL {
+                    let Some(expected) = example.answer(part) else {
+                        continue;
+                    };
+
+                    let answer = day.part(part)(&parsed).unwrap();
+
+                    if answer == NOT_YET_IMPLEMENTED {
+                        continue;
+                    }
+
+                    assert_eq!(
+                        answer, expected,
+                        "{} day {} part {:?}",
+                        year.year, day_number, part
+                    );
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn days_are_registered_in_order() {
+    let latest = days::latest_year();
+
+    assert!(days::find_year(latest).is_some_and(|year| !year.days.is_empty()));
+    assert!(days::find_year(1999).is_none());
+
+    for year in days::YEARS {
+        assert!(year
+            .days
+            .windows(2)
+            .all(|days| days[0].number < days[1].number));
+        assert_eq!(year.find_day(1).map(|day| day.number), Some(1));
+        assert!(year.find_day(days::LAST_DAY + 1).is_none());
+    }
+}
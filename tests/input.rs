@@ -0,0 +1,124 @@
+use std::{fs, io::Cursor, time::Duration};
+
+use advent_of_code_2024::{
+    client::{AocClient, ClientConfig, TooManyRequests},
+    input::{self, InputSource, RetrieveInputError},
+};
+use tiny_http::{Header, Request, Response};
+
+use crate::support::{stand_in::stand_in_server, temp_dir::TempDir};
+
+mod support;
+
+fn client(base_url: &str, cookie: Option<&str>) -> AocClient {
+    let config = ClientConfig {
+        base_url: base_url.to_string(),
+        user_agent: "aoc-tests (tests@example.com)".to_string(),
+        ..ClientConfig::default()
+    };
+
+    AocClient::new(&config, cookie.map(str::to_string)).unwrap()
+}
+
+/// Reply to each request for an input the way Advent of Code would, depending on the day.
+fn respond_with_input(request: &mut Request) -> Response<Cursor<Vec<u8>>> {
+    let header = |name| {
+        request
+            .headers()
+            .iter()
+            .find(|header| header.field.equiv(name))
+            .map(|header| header.value.to_string())
+    };
+
+    assert_eq!(header("Cookie").as_deref(), Some("session=abc"));
+    assert_eq!(
+        header("User-Agent").as_deref(),
+        Some("aoc-tests (tests@example.com)")
+    );
+
+    match request.url() {
+        "/2024/day/1/input" => Response::from_string("3   4\n4   3\n"),
+        "/2024/day/2/input" => Response::from_string(
+            "Puzzle inputs differ by user.  Please log in to get your puzzle input.\n",
+        )
+        .with_status_code(400),
+        "/2024/day/4/input" => Response::from_string("Too many requests")
+            .with_status_code(429)
+            .with_header("Retry-After: 120".parse::<Header>().unwrap()),
+        _ => Response::from_string(
+            "Please don't repeatedly request this endpoint before \
+             it unlocks! The calendar countdown is synchronized with the server time; the \
+             link will be enabled on the calendar the instant this puzzle becomes \
+             available.\n",
+        )
+        .with_status_code(404),
+    }
+}
+
+#[test]
+fn retrieves_cached_input_without_a_cookie() {
+    let cache_dir = TempDir::new("inputs-cache");
+    let cache = cache_dir.path();
+    fs::write(cache.join("day_1.txt"), "3   4\n4   3\n").unwrap();
+
+    // Nowhere to download from, so this would fail if it didn't use the cache.
+    let client = client("http://127.0.0.1:9", None);
+    let (input, source) = input::retrieve_input(&client, 2024, 1, cache, false).unwrap();
+
+    assert_eq!(input, "3   4\n4   3\n");
+    assert_eq!(source, InputSource::Cached);
+    assert!(matches!(
+        input::retrieve_input(&client, 2024, 2, cache, false),
+        Err(RetrieveInputError::NoCookieForDownload)
+    ));
+
+    // Errors cached by older versions aren't passed off as inputs.
+    fs::write(
+        cache.join("day_3.txt"),
+        "Puzzle inputs differ by user.  Please log in to get your puzzle input.\n",
+    )
+    .unwrap();
+    assert!(matches!(
+        input::retrieve_input(&client, 2024, 3, cache, false),
+        Err(RetrieveInputError::NotLoggedIn)
+    ));
+}
+
+#[test]
+fn downloads_inputs_from_stand_in_server() {
+    let cache_dir = TempDir::new("inputs-download");
+    let cache = cache_dir.path();
+    fs::write(cache.join("day_1.txt"), "garbage\n").unwrap();
+
+    let (base_url, server) = stand_in_server(4, respond_with_input);
+    let client = client(&base_url, Some("session=abc\n"));
+
+    let (input, source) = input::retrieve_input(&client, 2024, 1, cache, true).unwrap();
+
+    assert_eq!(input, "3   4\n4   3\n");
+    assert_eq!(source, InputSource::Downloaded);
+    assert_eq!(
+        fs::read_to_string(cache.join("day_1.txt")).unwrap(),
+        "3   4\n4   3\n"
+    );
+
+    assert!(matches!(
+        input::retrieve_input(&client, 2024, 2, cache, false),
+        Err(RetrieveInputError::NotLoggedIn)
+    ));
+    assert!(matches!(
+        input::retrieve_input(&client, 2024, 3, cache, false),
+        Err(RetrieveInputError::NotUnlocked)
+    ));
+    assert!(matches!(
+        input::retrieve_input(&client, 2024, 4, cache, false),
+        Err(RetrieveInputError::TooManyRequests(TooManyRequests {
+            retry_after: Some(retry_after)
+        })) if retry_after == Duration::from_secs(120)
+    ));
+    assert!(!cache.join("day_2.txt").exists());
+    assert!(!cache.join("day_3.txt").exists());
+    assert!(!cache.join("day_4.txt").exists());
+
+    server.join().unwrap();
+}
@@ -0,0 +1,8 @@
+//! What the tests share, kept here as the one copy of it. The integration tests include it as a
+//! module of their own, and the unit tests in the library and the binary include it by path.
+
+// Not every test crate uses everything in here.
+#![allow(dead_code)]
+
+pub mod stand_in;
+pub mod temp_dir;
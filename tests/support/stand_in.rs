@@ -0,0 +1,28 @@
+//! A stand-in for Advent of Code, served locally so that tests can talk to it over HTTP.
+
+use std::{
+    io::Cursor,
+    thread::{self, JoinHandle},
+};
+
+use tiny_http::{Request, Response, Server};
+
+/// Serve the next `requests` requests with whatever `respond` makes of each, returning the base
+/// URL to reach the server at and the thread it runs on. Joining that fails if `respond` panicked,
+/// so it can assert what it's sent.
+pub fn stand_in_server(
+    requests: usize,
+    respond: impl Fn(&mut Request) -> Response<Cursor<Vec<u8>>> + Send + 'static,
+) -> (String, JoinHandle<()>) {
+    let server = Server::http("127.0.0.1:0").unwrap();
+    let base_url = format!("http://{}", server.server_addr().to_ip().unwrap());
+
+    let handle = thread::spawn(move || {
+        for mut request in server.incoming_requests().take(requests) {
+            let response = respond(&mut request);
+            request.respond(response).unwrap();
+        }
+    });
+
+    (base_url, handle)
+}
@@ -0,0 +1,44 @@
+//! Scratch directories for tests that work with files, which clean up after themselves even when
+//! the test fails.
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+/// Tells apart the directories made by tests running at the same time in one process.
+static COUNT: AtomicUsize = AtomicUsize::new(0);
+
+/// An empty directory, removed along with everything in it when dropped.
+pub struct TempDir {
+    path: PathBuf,
+}
+
+impl TempDir {
+    /// Make a new directory, named after `name` so that any left behind can be traced to a test.
+    pub fn new(name: &str) -> Self {
+        let path = std::env::temp_dir().join(format!(
+            "aoc-{}-{}-{}",
+            name,
+            std::process::id(),
+            COUNT.fetch_add(1, Ordering::Relaxed)
+        ));
+
+        // Only a previous process with the same ID could have left this behind.
+        let _ = fs::remove_dir_all(&path);
+        fs::create_dir_all(&path).expect("Failed to create a temporary directory!");
+
+        TempDir { path }
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+impl Drop for TempDir {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.path);
+    }
+}
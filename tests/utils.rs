@@ -0,0 +1,16 @@
+use advent_of_code_2024::utils::{
+    direction::Direction,
+    pos::{Index2d, Pos},
+};
+
+#[test]
+fn moves_around_a_grid() {
+    let grid = vec![vec!['a', 'b'], vec!['c', 'd']];
+    let pos = Pos::new(0, 0) + Direction::Right + Direction::Down;
+
+    assert_eq!(pos, Pos::new(1, 1));
+    assert_eq!(grid.get_2d(pos), Some(&'d'));
+    assert_eq!(grid.get_2d(pos + Direction::Right), None);
+    assert!(pos.is_valid_grid_index(2, 2));
+    assert_eq!(Direction::Up.turned_right(), Direction::Right);
+}